@@ -1,6 +1,10 @@
 use crate::config::AppConfig;
 use crate::state::{AppState, NavigationView};
-use crate::ui::{FooterBar, HeaderBar, MemoryView, NavigationBar, PersonaPanel, SettingsView};
+use crate::ui::memory::BerryHealthMonitor;
+use crate::ui::{
+    FooterBar, HeaderBar, MemoryView, MemoryViewEvent, NavigationBar, PersonaPanel, SettingsEvent,
+    SettingsView,
+};
 use gpui::*;
 use gpui_component::{h_flex, v_flex, ActiveTheme};
 
@@ -22,8 +26,12 @@ impl App {
 
         let view = cx.entity().clone();
 
+        // Shared Berry health monitor for the footer and memory view
+        let berry_client = config.berry_client();
+        let berry_health = cx.new(|cx| BerryHealthMonitor::new(berry_client.clone(), cx));
+
         let header_bar = cx.new(|_cx| HeaderBar::new(window, _cx));
-        let footer_bar = cx.new(|_cx| FooterBar::new(window, _cx));
+        let footer_bar = cx.new(|cx| FooterBar::new(berry_health.clone(), window, cx));
 
         let nav_bar = cx.new(|_cx| {
             let view = view.clone();
@@ -43,14 +51,40 @@ impl App {
 
         let memory_view = cx.new(|cx| {
             MemoryView::new(
-                config.berry_server_url().to_string(),
+                berry_client,
+                &config.berry,
                 personas_for_memory,
+                berry_health,
                 window,
                 cx,
             )
         });
 
-        let _subscriptions = vec![];
+        // Keep the memory view connected to the active Berry profile, and the
+        // settings in step with the memory view's profile switcher
+        let _subscriptions = vec![
+            cx.subscribe_in(
+                &settings_view,
+                window,
+                |this, _settings, event: &SettingsEvent, window, cx| match event {
+                    SettingsEvent::BerryChanged => {
+                        this.memory_view.update(cx, |memory_view, cx| {
+                            memory_view.reload_berry(window, cx);
+                        });
+                    }
+                },
+            ),
+            cx.subscribe(
+                &memory_view,
+                |this, _memory_view, event: &MemoryViewEvent, cx| match event {
+                    MemoryViewEvent::ProfileSwitched(name) => {
+                        this.settings_view.update(cx, |settings_view, cx| {
+                            settings_view.set_active_berry_profile(name, cx);
+                        });
+                    }
+                },
+            ),
+        ];
 
         Self {
             state,
@@ -1,5 +1,6 @@
 use super::terminal::TerminalConfig;
-use super::{BerryConfig, GeneralConfig, PersonasConfig};
+use super::{BerryConfig, BerryProfile, GeneralConfig, PersonasConfig, Secrets, TablesConfig};
+use crate::memory::{BerryAuth, BerryClient};
 use crate::persona::Persona;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -20,27 +21,22 @@ pub struct AppConfig {
 
     /// Personas configuration
     pub personas: PersonasConfig,
+
+    /// Table sort order and column widths
+    pub tables: TablesConfig,
 }
 
 impl AppConfig {
     /// Load configuration from the default config file location.
     /// Environment variables override file values for specific settings.
     pub fn load() -> Self {
-        // First load from TOML file
-        let mut config: Self = Self::config_path()
-            .and_then(|path| {
-                if path.exists() {
-                    std::fs::read_to_string(&path).ok()
-                } else {
-                    None
-                }
-            })
-            .and_then(|content| toml::from_str(&content).ok())
-            .unwrap_or_default();
+        let mut config = Self::load_file();
 
         // Apply environment variable overrides
-        if let Ok(url) = std::env::var("BERRY_SERVER_URL") {
-            config.berry.server_url = url;
+        for (index, profile) in config.berry.profiles.iter_mut().enumerate() {
+            if let Some(url) = berry_override("BERRY_SERVER_URL", index, &profile.name, env_var) {
+                profile.server_url = url;
+            }
         }
 
         if let Ok(dir) = std::env::var("PERSONAS_DIR") {
@@ -50,6 +46,29 @@ impl AppConfig {
         config
     }
 
+    /// Load the configuration exactly as written in the config file, without
+    /// environment variable overrides
+    pub fn load_file() -> Self {
+        Self::config_path()
+            .and_then(|path| {
+                if path.exists() {
+                    std::fs::read_to_string(&path).ok()
+                } else {
+                    None
+                }
+            })
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Change the config file and save it, so values that only come from
+    /// environment variables are never written to it
+    pub fn update(change: impl FnOnce(&mut Self)) -> Result<(), std::io::Error> {
+        let mut config = Self::load_file();
+        change(&mut config);
+        config.save()
+    }
+
     /// Save the configuration to the default config file location
     pub fn save(&self) -> Result<(), std::io::Error> {
         let Some(config_path) = Self::config_path() else {
@@ -74,9 +93,30 @@ impl AppConfig {
         dirs::config_dir().map(|p| p.join("persona").join("config.toml"))
     }
 
-    /// Convenience accessor for berry server URL
+    /// Convenience accessor for the active berry server URL
     pub fn berry_server_url(&self) -> &str {
-        &self.berry.server_url
+        &self.berry.active().server_url
+    }
+
+    /// Credentials for the active Berry profile, with the token from the environment,
+    /// see `berry_override`, or the secrets file
+    pub fn berry_auth(&self) -> BerryAuth {
+        let profile = self.berry.active();
+        let token = Self::env_token(&self.berry, &profile.name)
+            .or_else(|| Secrets::load().token_for(&profile.name));
+        profile.credentials(token)
+    }
+
+    /// Token for a Berry profile set in the environment, which wins over the
+    /// secrets file
+    pub fn env_token(berry: &BerryConfig, profile: &str) -> Option<String> {
+        let index = berry.profiles.iter().position(|p| p.name == profile)?;
+        berry_override("BERRY_TOKEN", index, profile, env_var)
+    }
+
+    /// Client for the active Berry profile
+    pub fn berry_client(&self) -> BerryClient {
+        BerryClient::new(self.berry_server_url()).with_auth(&self.berry_auth())
     }
 
     /// Convenience accessor for personas directory
@@ -152,11 +192,77 @@ impl AppConfig {
     }
 }
 
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Environment override of a Berry profile setting
+///
+/// `<NAME>_<PROFILE>` applies to one profile, e.g. `BERRY_TOKEN_WORK` for the profile
+/// named `work`. Plain `<NAME>` only applies to the first profile, the one a config
+/// from before profiles existed becomes, so it never leaks into other servers.
+fn berry_override(
+    name: &str,
+    index: usize,
+    profile: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let suffix: String = profile
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    lookup(&format!("{}_{}", name, suffix))
+        .or_else(|| lookup(name).filter(|_| index == 0))
+        .filter(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    mod env_override_tests {
+        use super::*;
+
+        fn lookup(name: &str) -> Option<String> {
+            match name {
+                "BERRY_TOKEN" => Some("shared".to_string()),
+                "BERRY_TOKEN_WORK_BERRY" => Some("work".to_string()),
+                "BERRY_SERVER_URL_EMPTY" => Some(String::new()),
+                _ => None,
+            }
+        }
+
+        #[test]
+        fn test_plain_override_only_applies_to_first_profile() {
+            assert_eq!(
+                berry_override("BERRY_TOKEN", 0, "default", lookup).as_deref(),
+                Some("shared")
+            );
+            assert_eq!(berry_override("BERRY_TOKEN", 1, "personal", lookup), None);
+        }
+
+        #[test]
+        fn test_profile_override_wins() {
+            assert_eq!(
+                berry_override("BERRY_TOKEN", 0, "work berry", lookup).as_deref(),
+                Some("work")
+            );
+            assert_eq!(
+                berry_override("BERRY_TOKEN", 2, "Work-Berry", lookup).as_deref(),
+                Some("work")
+            );
+            assert_eq!(berry_override("BERRY_SERVER_URL", 1, "empty", lookup), None);
+        }
+    }
+
     mod default_tests {
         use super::*;
 
@@ -169,7 +275,7 @@ mod tests {
             assert_eq!(config.terminal.font_size, 14.0);
 
             // Berry defaults
-            assert_eq!(config.berry.server_url, "http://localhost:4114");
+            assert_eq!(config.berry_server_url(), "http://localhost:4114");
 
             // Personas directory should be set
             assert!(!config.personas.directory.as_os_str().is_empty());
@@ -194,9 +300,11 @@ mod tests {
                     font_size: 16.0,
                     ..Default::default()
                 },
-                berry: BerryConfig {
+                berry: BerryProfile {
                     server_url: "http://custom:8080".to_string(),
-                },
+                    ..Default::default()
+                }
+                .into(),
                 personas: PersonasConfig {
                     directory: PathBuf::from("/custom/path"),
                 },
@@ -208,7 +316,7 @@ mod tests {
 
             assert_eq!(parsed.terminal.font_family, "Hack");
             assert_eq!(parsed.terminal.font_size, 16.0);
-            assert_eq!(parsed.berry.server_url, "http://custom:8080");
+            assert_eq!(parsed.berry_server_url(), "http://custom:8080");
             assert_eq!(parsed.personas.directory, PathBuf::from("/custom/path"));
         }
 
@@ -235,7 +343,7 @@ mod tests {
             assert_eq!(config.terminal.font_family, "JetBrains Mono");
             assert_eq!(config.terminal.font_size, 18.0);
             assert_eq!(config.terminal.scrollback, 5000);
-            assert_eq!(config.berry.server_url, "https://berry.example.com");
+            assert_eq!(config.berry_server_url(), "https://berry.example.com");
             assert_eq!(
                 config.personas.directory,
                 PathBuf::from("/home/user/personas")
@@ -252,7 +360,7 @@ mod tests {
             let config = AppConfig::from_toml(toml_str).expect("Failed to deserialize");
 
             // Berry should be custom
-            assert_eq!(config.berry.server_url, "http://custom:9999");
+            assert_eq!(config.berry_server_url(), "http://custom:9999");
 
             // Terminal should use defaults
             assert_eq!(config.terminal.font_family, "monospace");
@@ -265,7 +373,7 @@ mod tests {
             let config = AppConfig::from_toml(toml_str).expect("Failed to deserialize");
 
             assert_eq!(config.terminal.font_family, "monospace");
-            assert_eq!(config.berry.server_url, "http://localhost:4114");
+            assert_eq!(config.berry_server_url(), "http://localhost:4114");
         }
     }
 
@@ -283,9 +391,11 @@ mod tests {
                     font_size: 20.0,
                     ..Default::default()
                 },
-                berry: BerryConfig {
+                berry: BerryProfile {
                     server_url: "http://test:1234".to_string(),
-                },
+                    ..Default::default()
+                }
+                .into(),
                 personas: PersonasConfig {
                     directory: PathBuf::from("/test/personas"),
                 },
@@ -300,7 +410,7 @@ mod tests {
 
             assert_eq!(loaded.terminal.font_family, "Test Font");
             assert_eq!(loaded.terminal.font_size, 20.0);
-            assert_eq!(loaded.berry.server_url, "http://test:1234");
+            assert_eq!(loaded.berry_server_url(), "http://test:1234");
             assert_eq!(loaded.personas.directory, PathBuf::from("/test/personas"));
         }
 
@@ -310,7 +420,7 @@ mod tests {
             let config = AppConfig::load_from_path(&config_path);
 
             assert_eq!(config.terminal.font_family, "monospace");
-            assert_eq!(config.berry.server_url, "http://localhost:4114");
+            assert_eq!(config.berry_server_url(), "http://localhost:4114");
         }
 
         #[test]
@@ -1,17 +1,181 @@
+use crate::memory::BerryAuth;
 use serde::{Deserialize, Serialize};
 
-/// Berry server configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Name of the profile created from a config written before profiles existed
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// How the Berry token is sent with each request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BerryAuthScheme {
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>`
+    Bearer,
+    /// Token as the value of `auth_header`
+    Header,
+}
+
+impl BerryAuthScheme {
+    pub fn all() -> &'static [BerryAuthScheme] {
+        &[
+            BerryAuthScheme::None,
+            BerryAuthScheme::Bearer,
+            BerryAuthScheme::Header,
+        ]
+    }
+}
+
+impl std::fmt::Display for BerryAuthScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BerryAuthScheme::None => write!(f, "None"),
+            BerryAuthScheme::Bearer => write!(f, "Bearer Token"),
+            BerryAuthScheme::Header => write!(f, "Custom Header"),
+        }
+    }
+}
+
+/// A named Berry server
+///
+/// The token itself is kept in the secrets file, see `Secrets`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
-pub struct BerryConfig {
+pub struct BerryProfile {
+    /// Name shown in the profile switcher
+    pub name: String,
+
     /// URL of the Berry server
     pub server_url: String,
+
+    /// How the token is sent
+    pub auth: BerryAuthScheme,
+
+    /// Header carrying the token when `auth` is `header`
+    pub auth_header: String,
+
+    /// Actor the memory view browses as, the UI actor when unset
+    pub default_actor: Option<String>,
 }
 
-impl Default for BerryConfig {
+impl Default for BerryProfile {
     fn default() -> Self {
         Self {
+            name: DEFAULT_PROFILE.to_string(),
             server_url: "http://localhost:4114".to_string(),
+            auth: BerryAuthScheme::None,
+            auth_header: "X-Berry-Token".to_string(),
+            default_actor: None,
+        }
+    }
+}
+
+impl BerryProfile {
+    /// Credentials for the client, none when the scheme needs a token and there isn't one
+    pub fn credentials(&self, token: Option<String>) -> BerryAuth {
+        let Some(token) = token.filter(|t| !t.trim().is_empty()) else {
+            return BerryAuth::None;
+        };
+        match self.auth {
+            BerryAuthScheme::None => BerryAuth::None,
+            BerryAuthScheme::Bearer => BerryAuth::Bearer(token),
+            BerryAuthScheme::Header => BerryAuth::Header {
+                name: self.auth_header.clone(),
+                token,
+            },
+        }
+    }
+}
+
+/// Berry server profiles and the one in use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "BerryConfigFile")]
+pub struct BerryConfig {
+    /// Name of the profile in use
+    pub active_profile: String,
+
+    /// Known servers, never empty
+    pub profiles: Vec<BerryProfile>,
+}
+
+impl Default for BerryConfig {
+    fn default() -> Self {
+        BerryProfile::default().into()
+    }
+}
+
+impl From<BerryProfile> for BerryConfig {
+    fn from(profile: BerryProfile) -> Self {
+        Self {
+            active_profile: profile.name.clone(),
+            profiles: vec![profile],
+        }
+    }
+}
+
+impl BerryConfig {
+    /// The profile in use, falling back to the first one
+    pub fn active(&self) -> &BerryProfile {
+        self.profile(&self.active_profile)
+            .unwrap_or(&self.profiles[0])
+    }
+
+    pub fn active_mut(&mut self) -> &mut BerryProfile {
+        let index = self
+            .profiles
+            .iter()
+            .position(|p| p.name == self.active_profile)
+            .unwrap_or(0);
+        &mut self.profiles[index]
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&BerryProfile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Make a profile active, returning false if there is no profile with that name
+    pub fn set_active(&mut self, name: &str) -> bool {
+        if self.profile(name).is_none() {
+            return false;
+        }
+        self.active_profile = name.to_string();
+        true
+    }
+
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.iter().map(|p| p.name.clone()).collect()
+    }
+}
+
+/// `[berry]` as written in `config.toml`, which may still hold just the
+/// `server_url` from before profiles
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BerryConfigFile {
+    active_profile: Option<String>,
+    profiles: Vec<BerryProfile>,
+    server_url: Option<String>,
+}
+
+impl From<BerryConfigFile> for BerryConfig {
+    fn from(file: BerryConfigFile) -> Self {
+        let mut profiles = file.profiles;
+        if profiles.is_empty() {
+            let defaults = BerryProfile::default();
+            profiles.push(BerryProfile {
+                server_url: file.server_url.unwrap_or(defaults.server_url),
+                ..defaults
+            });
+        }
+
+        let active_profile = file
+            .active_profile
+            .filter(|name| profiles.iter().any(|p| &p.name == name))
+            .unwrap_or_else(|| profiles[0].name.clone());
+
+        Self {
+            active_profile,
+            profiles,
         }
     }
 }
@@ -20,38 +184,108 @@ impl Default for BerryConfig {
 mod tests {
     use super::*;
 
+    fn profile(name: &str, server_url: &str) -> BerryProfile {
+        BerryProfile {
+            name: name.to_string(),
+            server_url: server_url.to_string(),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_default_values() {
         let config = BerryConfig::default();
-        assert_eq!(config.server_url, "http://localhost:4114");
+        assert_eq!(config.active_profile, DEFAULT_PROFILE);
+        assert_eq!(config.active().server_url, "http://localhost:4114");
+        assert_eq!(config.active().auth, BerryAuthScheme::None);
     }
 
     #[test]
     fn test_serialization_roundtrip() {
         let config = BerryConfig {
-            server_url: "http://example.com:8080".to_string(),
+            active_profile: "personal".to_string(),
+            profiles: vec![
+                profile("work", "https://berry.work.example.com"),
+                BerryProfile {
+                    auth: BerryAuthScheme::Header,
+                    default_actor: Some("staff-mentor".to_string()),
+                    ..profile("personal", "http://localhost:4114")
+                },
+            ],
         };
 
         let toml_str = toml::to_string(&config).expect("Failed to serialize");
         let parsed: BerryConfig = toml::from_str(&toml_str).expect("Failed to deserialize");
 
-        assert_eq!(parsed.server_url, config.server_url);
+        assert_eq!(parsed.active_profile, "personal");
+        assert_eq!(parsed.profiles, config.profiles);
+        assert_eq!(parsed.active().auth, BerryAuthScheme::Header);
     }
 
     #[test]
-    fn test_deserialize_from_toml() {
+    fn test_deserialize_single_server_config() {
         let toml_str = r#"
             server_url = "https://berry.example.com"
         "#;
 
         let config: BerryConfig = toml::from_str(toml_str).expect("Failed to deserialize");
-        assert_eq!(config.server_url, "https://berry.example.com");
+        assert_eq!(config.profiles.len(), 1);
+        assert_eq!(config.active_profile, DEFAULT_PROFILE);
+        assert_eq!(config.active().server_url, "https://berry.example.com");
+        assert_eq!(config.active().auth, BerryAuthScheme::None);
     }
 
     #[test]
     fn test_deserialize_empty_uses_defaults() {
         let toml_str = "";
         let config: BerryConfig = toml::from_str(toml_str).expect("Failed to deserialize");
-        assert_eq!(config.server_url, "http://localhost:4114");
+        assert_eq!(config.active().server_url, "http://localhost:4114");
+        assert_eq!(config.active().auth, BerryAuthScheme::None);
+    }
+
+    #[test]
+    fn test_unknown_active_profile_falls_back_to_first() {
+        let toml_str = r#"
+            active_profile = "gone"
+
+            [[profiles]]
+            name = "work"
+            server_url = "https://berry.work.example.com"
+
+            [[profiles]]
+            name = "personal"
+        "#;
+
+        let mut config: BerryConfig = toml::from_str(toml_str).expect("Failed to deserialize");
+        assert_eq!(config.active().name, "work");
+
+        assert!(config.set_active("personal"));
+        assert_eq!(config.active().server_url, "http://localhost:4114");
+        assert!(!config.set_active("gone"));
+        assert_eq!(config.active_profile, "personal");
+    }
+
+    #[test]
+    fn test_credentials() {
+        let mut profile = BerryProfile {
+            auth: BerryAuthScheme::Bearer,
+            ..Default::default()
+        };
+        assert_eq!(
+            profile.credentials(Some("abc".to_string())),
+            BerryAuth::Bearer("abc".to_string())
+        );
+        assert_eq!(profile.credentials(Some("  ".to_string())), BerryAuth::None);
+        assert_eq!(profile.credentials(None), BerryAuth::None);
+
+        profile.auth = BerryAuthScheme::Header;
+        profile.auth_header = "X-Api-Key".to_string();
+        assert_eq!(
+            profile.credentials(Some("abc".to_string())),
+            BerryAuth::Header {
+                name: "X-Api-Key".to_string(),
+                token: "abc".to_string()
+            }
+        );
     }
 }
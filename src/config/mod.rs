@@ -3,11 +3,15 @@ mod berry;
 mod data;
 mod general;
 mod personas;
+mod secrets;
+mod tables;
 mod terminal;
 
 pub use app::AppConfig;
-pub use berry::BerryConfig;
-pub use data::{ensure_data_dir, working_dir};
+pub use berry::{BerryAuthScheme, BerryConfig, BerryProfile, DEFAULT_PROFILE};
+pub use data::{data_dir, ensure_data_dir, working_dir};
 pub use general::GeneralConfig;
 pub use personas::PersonasConfig;
+pub use secrets::Secrets;
+pub use tables::{TableLayoutConfig, TablesConfig};
 pub use terminal::{TerminalConfig, TerminalThemeConfig};
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Credentials kept out of `config.toml`, in a file only the user can read
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct Secrets {
+    /// Berry tokens by profile name
+    pub berry_tokens: BTreeMap<String, String>,
+}
+
+impl Secrets {
+    /// Token for a Berry profile
+    pub fn token_for(&self, profile: &str) -> Option<String> {
+        self.berry_tokens.get(profile).cloned()
+    }
+
+    /// Set or clear the token for a Berry profile
+    pub fn set_token(&mut self, profile: &str, token: Option<String>) {
+        match token.filter(|t| !t.trim().is_empty()) {
+            Some(token) => {
+                self.berry_tokens.insert(profile.to_string(), token);
+            }
+            None => {
+                self.berry_tokens.remove(profile);
+            }
+        }
+    }
+
+    /// Load secrets from the default location, empty if the file doesn't exist
+    pub fn load() -> Self {
+        Self::path()
+            .map(|path| Self::load_from_path(&path))
+            .unwrap_or_default()
+    }
+
+    /// Save secrets to the default location, readable only by the user
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let Some(path) = Self::path() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine config directory",
+            ));
+        };
+        self.save_to_path(&path)
+    }
+
+    /// Get the default secrets file path, next to `config.toml`
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("persona").join("secrets.toml"))
+    }
+
+    pub fn load_from_path(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+        warn_if_readable_by_others(path);
+
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let toml_str = toml::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path)?;
+
+        // The mode only applies to new files, so tighten an existing one too
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        file.write_all(toml_str.as_bytes())
+    }
+}
+
+#[cfg(unix)]
+fn warn_if_readable_by_others(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    if let Ok(metadata) = std::fs::metadata(path) {
+        if metadata.permissions().mode() & 0o077 != 0 {
+            eprintln!(
+                "Warning: {:?} is readable by other users, run `chmod 600` on it",
+                path
+            );
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn warn_if_readable_by_others(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let secrets = Secrets::load_from_path(&temp_dir.path().join("secrets.toml"));
+        assert_eq!(secrets, Secrets::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("persona").join("secrets.toml");
+
+        let mut secrets = Secrets::default();
+        secrets.set_token("default", Some("s3cret".to_string()));
+        secrets.set_token("work", Some("w0rk".to_string()));
+        secrets.save_to_path(&path).expect("Failed to save secrets");
+
+        assert_eq!(Secrets::load_from_path(&path), secrets);
+    }
+
+    #[test]
+    fn test_token_per_profile() {
+        let mut secrets = Secrets::default();
+        assert_eq!(secrets.token_for("work"), None);
+
+        secrets.set_token("work", Some("w0rk".to_string()));
+        secrets.set_token("personal", Some("p3rsonal".to_string()));
+        assert_eq!(secrets.token_for("work").as_deref(), Some("w0rk"));
+        assert_eq!(secrets.token_for("personal").as_deref(), Some("p3rsonal"));
+
+        secrets.set_token("work", Some("  ".to_string()));
+        assert_eq!(secrets.token_for("work"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_saved_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("secrets.toml");
+        std::fs::write(&path, "").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        Secrets::default()
+            .save_to_path(&path)
+            .expect("Failed to save secrets");
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Column layout of the data tables
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TablesConfig {
+    /// Berry memory table
+    pub memory: TableLayoutConfig,
+
+    /// Knowledgebase document table
+    pub knowledgebase: TableLayoutConfig,
+}
+
+/// Sort order and column widths of a single table
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct TableLayoutConfig {
+    /// ID of the column the table is sorted by
+    pub sort_column: Option<String>,
+
+    /// Whether the sort is descending
+    pub sort_descending: bool,
+
+    /// Column widths in pixels keyed by column ID
+    pub column_widths: BTreeMap<String, f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_values() {
+        let config = TablesConfig::default();
+        assert!(config.memory.sort_column.is_none());
+        assert!(!config.memory.sort_descending);
+        assert!(config.knowledgebase.column_widths.is_empty());
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let config = TablesConfig {
+            memory: TableLayoutConfig {
+                sort_column: Some("created".to_string()),
+                sort_descending: true,
+                column_widths: BTreeMap::from([("author".to_string(), 240.0)]),
+            },
+            ..Default::default()
+        };
+
+        let toml_str = toml::to_string(&config).expect("Failed to serialize");
+        let parsed: TablesConfig = toml::from_str(&toml_str).expect("Failed to deserialize");
+
+        assert_eq!(parsed.memory, config.memory);
+        assert_eq!(parsed.knowledgebase, TableLayoutConfig::default());
+    }
+
+    #[test]
+    fn test_deserialize_from_toml() {
+        let toml_str = r#"
+            [memory]
+            sort_column = "tags"
+
+            [memory.column_widths]
+            id = 120.0
+        "#;
+
+        let config: TablesConfig = toml::from_str(toml_str).expect("Failed to deserialize");
+        assert_eq!(config.memory.sort_column.as_deref(), Some("tags"));
+        assert_eq!(config.memory.column_widths.get("id"), Some(&120.0));
+    }
+}
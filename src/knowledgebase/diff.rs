@@ -0,0 +1,114 @@
+/// A line of a diff between two versions of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    /// Only in the old version
+    Removed(String),
+    /// Only in the new version
+    Added(String),
+}
+
+/// Line-by-line diff from `old` to `new`, based on their longest common subsequence
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Leading and trailing lines that match are kept out of the table
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lengths[i][j] is the longest common subsequence of old_mid[i..] and new_mid[j..]
+    let mut lengths = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lengths[i][j] = if old_mid[i] == new_mid[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line.to_string()))
+        .collect();
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() && j < new_mid.len() {
+        if old_mid[i] == new_mid[j] {
+            lines.push(DiffLine::Same(old_mid[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(DiffLine::Removed(old_mid[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new_mid[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(
+        old_mid[i..]
+            .iter()
+            .map(|l| DiffLine::Removed(l.to_string())),
+    );
+    lines.extend(new_mid[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line.to_string())),
+    );
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "# Review\n\n- naming\n- tests\n";
+        let new = "# Review\n\n- naming\n- error handling\n- tests\n- docs\n";
+
+        assert_eq!(
+            diff_lines(old, new),
+            vec![
+                DiffLine::Same("# Review".to_string()),
+                DiffLine::Same(String::new()),
+                DiffLine::Same("- naming".to_string()),
+                DiffLine::Added("- error handling".to_string()),
+                DiffLine::Same("- tests".to_string()),
+                DiffLine::Added("- docs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_replaced_and_removed_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nx\n"),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Removed("c".to_string()),
+                DiffLine::Added("x".to_string()),
+            ]
+        );
+        assert!(diff_lines("same", "same")
+            .iter()
+            .all(|line| matches!(line, DiffLine::Same(_))));
+        assert_eq!(
+            diff_lines("", "new"),
+            vec![DiffLine::Added("new".to_string())]
+        );
+    }
+}
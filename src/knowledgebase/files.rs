@@ -0,0 +1,268 @@
+use super::{create_unique, slugify};
+use anyhow::{bail, Context};
+use chrono::Local;
+use std::path::{Component, Path, PathBuf};
+
+/// Folder inside each knowledgebase that deleted documents are moved to
+pub const TRASH_DIR: &str = ".trash";
+
+/// Resolve a path given relative to a knowledgebase, refusing anything that would
+/// end up outside it or in a hidden folder such as the trash
+fn resolve(kb_path: &Path, relative: &Path) -> anyhow::Result<PathBuf> {
+    for component in relative.components() {
+        match component {
+            Component::Normal(name) if name.to_string_lossy().starts_with('.') => {
+                bail!("{} is a hidden path", relative.display())
+            }
+            Component::Normal(_) | Component::CurDir => {}
+            _ => bail!("{} is outside the knowledgebase", relative.display()),
+        }
+    }
+
+    let path = kb_path.join(relative);
+    ensure_inside(kb_path, &path)?;
+    Ok(path)
+}
+
+/// Check that a path is inside a knowledgebase once symlinks are resolved
+fn ensure_inside(kb_path: &Path, path: &Path) -> anyhow::Result<()> {
+    let root = kb_path
+        .canonicalize()
+        .with_context(|| format!("Knowledgebase {} not found", kb_path.display()))?;
+
+    // The path may not exist yet, so resolve its closest existing ancestor
+    let mut existing = path;
+    while !existing.exists() {
+        if existing.file_name().is_none() {
+            bail!("{} is outside the knowledgebase", path.display());
+        }
+        existing = existing.parent().unwrap_or(Path::new(""));
+    }
+
+    let resolved = existing.canonicalize()?;
+    if !resolved.starts_with(&root) {
+        bail!("{} is outside the knowledgebase", path.display());
+    }
+    Ok(())
+}
+
+/// Path of a file relative to its knowledgebase
+fn relative_to(kb_path: &Path, path: &Path) -> anyhow::Result<PathBuf> {
+    ensure_inside(kb_path, path)?;
+    path.strip_prefix(kb_path)
+        .map(Path::to_path_buf)
+        .with_context(|| format!("{} is outside the knowledgebase", path.display()))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "untitled".to_string())
+}
+
+/// Write a document's new content, refusing files that resolve outside the
+/// knowledgebase such as symlinks to elsewhere
+pub fn save_file(kb_path: &Path, path: &Path, content: &str) -> anyhow::Result<()> {
+    relative_to(kb_path, path)?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Create an empty document titled `title` in a folder of the knowledgebase
+pub fn new_document(kb_path: &Path, folder: &Path, title: &str) -> anyhow::Result<PathBuf> {
+    let title = title.trim();
+    if title.is_empty() {
+        bail!("A title is required");
+    }
+
+    let dir = resolve(kb_path, folder)?;
+    std::fs::create_dir_all(&dir)?;
+    create_unique(&dir, &slugify(title), &format!("# {}\n\n", title))
+}
+
+/// Rename a document, `new_name` being a file name or a path relative to the
+/// knowledgebase with or without the `.md` extension
+pub fn rename_file(kb_path: &Path, path: &Path, new_name: &str) -> anyhow::Result<PathBuf> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        bail!("A name is required");
+    }
+    relative_to(kb_path, path)?;
+
+    let relative = if new_name.ends_with(".md") {
+        PathBuf::from(new_name)
+    } else {
+        PathBuf::from(format!("{}.md", new_name))
+    };
+    // A bare file name stays in the same folder
+    let relative = match (relative.parent(), path.parent()) {
+        (Some(parent), Some(folder)) if parent.as_os_str().is_empty() => folder
+            .strip_prefix(kb_path)
+            .unwrap_or(Path::new(""))
+            .join(&relative),
+        _ => relative,
+    };
+
+    let target = resolve(kb_path, &relative)?;
+    if target == path {
+        return Ok(target);
+    }
+    if target.exists() {
+        bail!("{} already exists", relative.display());
+    }
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(path, &target)?;
+    Ok(target)
+}
+
+/// Copy a document next to the original as `<name>-copy.md`
+pub fn duplicate_file(kb_path: &Path, path: &Path) -> anyhow::Result<PathBuf> {
+    relative_to(kb_path, path)?;
+    let content = std::fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or(kb_path);
+    create_unique(dir, &format!("{}-copy", file_stem(path)), &content)
+}
+
+/// Move a document into another knowledgebase, keeping its folder and adding a
+/// number if the name is taken there
+pub fn move_file(from_kb: &Path, path: &Path, to_kb: &Path) -> anyhow::Result<PathBuf> {
+    let relative = relative_to(from_kb, path)?;
+    let folder = relative.parent().unwrap_or(Path::new(""));
+    let dir = resolve(to_kb, folder)?;
+    std::fs::create_dir_all(&dir)?;
+
+    // Copy then remove so moves work across filesystems
+    let content = std::fs::read_to_string(path)?;
+    let target = create_unique(&dir, &file_stem(path), &content)?;
+    std::fs::remove_file(path)?;
+    Ok(target)
+}
+
+/// Move a document into the knowledgebase's trash folder, keeping its folder and
+/// stamping the name so repeated deletes don't collide
+pub fn trash_file(kb_path: &Path, path: &Path) -> anyhow::Result<PathBuf> {
+    let relative = relative_to(kb_path, path)?;
+    let folder = relative.parent().unwrap_or(Path::new(""));
+    let dir = kb_path.join(TRASH_DIR).join(folder);
+    std::fs::create_dir_all(&dir)?;
+
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let content = std::fs::read_to_string(path)?;
+    let target = create_unique(&dir, &format!("{}-{}", file_stem(path), stamp), &content)?;
+    std::fs::remove_file(path)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::load_entries;
+    use super::*;
+    use tempfile::TempDir;
+
+    fn knowledgebase(temp_dir: &TempDir, name: &str) -> PathBuf {
+        let path = temp_dir.path().join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_paths_outside_the_knowledgebase_are_rejected() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let kb = knowledgebase(&temp_dir, "mentor");
+
+        assert!(resolve(&kb, Path::new("projects/notes.md")).is_ok());
+        assert!(resolve(&kb, Path::new("../assistant/notes.md")).is_err());
+        assert!(resolve(&kb, Path::new("/etc/passwd")).is_err());
+        assert!(resolve(&kb, Path::new(".trash/notes.md")).is_err());
+
+        let outside = temp_dir.path().join("outside.md");
+        std::fs::write(&outside, "# Outside\n").unwrap();
+        assert!(rename_file(&kb, &outside, "inside").is_err());
+        assert!(trash_file(&kb, &outside).is_err());
+        assert!(outside.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_out_of_the_knowledgebase_is_rejected() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let kb = knowledgebase(&temp_dir, "mentor");
+        let elsewhere = knowledgebase(&temp_dir, "elsewhere");
+        std::os::unix::fs::symlink(&elsewhere, kb.join("linked")).unwrap();
+
+        assert!(new_document(&kb, Path::new("linked"), "Escape").is_err());
+        assert!(std::fs::read_dir(&elsewhere).unwrap().next().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_saving_through_a_symlink_out_of_the_knowledgebase_is_rejected() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let kb = knowledgebase(&temp_dir, "mentor");
+        let outside = temp_dir.path().join("outside.md");
+        std::fs::write(&outside, "# Outside\n").unwrap();
+        let link = kb.join("notes.md");
+        std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+        assert!(save_file(&kb, &link, "# Changed\n").is_err());
+        assert_eq!(std::fs::read_to_string(&outside).unwrap(), "# Outside\n");
+        // The link isn't listed, so it can't be opened in the first place
+        assert!(load_entries(&kb).is_empty());
+
+        let inside = new_document(&kb, Path::new(""), "Inside").unwrap();
+        save_file(&kb, &inside, "# Inside\n\nEdited\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&inside).unwrap(),
+            "# Inside\n\nEdited\n"
+        );
+    }
+
+    #[test]
+    fn test_new_document_and_rename() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let kb = knowledgebase(&temp_dir, "mentor");
+
+        let path = new_document(&kb, Path::new("projects"), "Berry API").unwrap();
+        assert_eq!(path, kb.join("projects").join("berry-api.md"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Berry API\n\n");
+        assert!(new_document(&kb, Path::new(""), "  ").is_err());
+
+        let renamed = rename_file(&kb, &path, "endpoints").unwrap();
+        assert_eq!(renamed, kb.join("projects").join("endpoints.md"));
+        assert!(!path.exists());
+
+        let moved = rename_file(&kb, &renamed, "archive/endpoints.md").unwrap();
+        assert_eq!(moved, kb.join("archive").join("endpoints.md"));
+
+        let other = new_document(&kb, Path::new("archive"), "Other").unwrap();
+        assert!(rename_file(&kb, &other, "archive/endpoints").is_err());
+    }
+
+    #[test]
+    fn test_duplicate_move_and_trash() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let mentor = knowledgebase(&temp_dir, "mentor");
+        let assistant = knowledgebase(&temp_dir, "assistant");
+        let path = new_document(&mentor, Path::new("notes"), "Standup").unwrap();
+
+        let copy = duplicate_file(&mentor, &path).unwrap();
+        assert_eq!(copy, mentor.join("notes").join("standup-copy.md"));
+        assert_eq!(
+            std::fs::read_to_string(&copy).unwrap(),
+            std::fs::read_to_string(&path).unwrap()
+        );
+
+        let moved = move_file(&mentor, &copy, &assistant).unwrap();
+        assert_eq!(moved, assistant.join("notes").join("standup-copy.md"));
+        assert!(!copy.exists());
+
+        let trashed = trash_file(&mentor, &path).unwrap();
+        assert!(trashed.starts_with(mentor.join(TRASH_DIR).join("notes")));
+        assert!(!path.exists());
+        // The trash is hidden from the knowledgebase
+        assert!(load_entries(&mentor).is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+use super::KnowledgebaseEntry;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+
+/// Optional YAML block at the top of a knowledgebase document, between `---` lines
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "FrontmatterFile", into = "FrontmatterFile")]
+pub struct Frontmatter {
+    pub tags: Vec<String>,
+    pub status: Option<String>,
+    pub summary: Option<String>,
+    /// Persona the document is about or written for
+    pub persona: Option<String>,
+    /// The persona was read from `related_persona` and is written back under it
+    pub related_persona_key: bool,
+    /// Keys the app doesn't use, kept as they are when the frontmatter is rewritten
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// Frontmatter as written in the document, any YAML value being accepted for the
+/// keys the app reads
+#[derive(Serialize, Deserialize)]
+struct FrontmatterFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tags: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    persona: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    related_persona: Option<Value>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, Value>,
+}
+
+/// Text of a scalar, so `status: 1` reads as "1"
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+impl From<FrontmatterFile> for Frontmatter {
+    fn from(file: FrontmatterFile) -> Self {
+        let mut extra = file.extra;
+        // Lists and maps where text is expected are kept with the unknown keys
+        let mut text = |key: &str, value: Option<Value>| {
+            let value = value.filter(|value| !value.is_null())?;
+            let text = scalar_text(&value);
+            if text.is_none() {
+                extra.insert(key.to_string(), value);
+            }
+            text
+        };
+        let status = text("status", file.status);
+        let summary = text("summary", file.summary);
+        let persona = text("persona", file.persona);
+        let related_persona = text("related_persona", file.related_persona);
+
+        // Tags written either as a YAML list or as one comma separated string
+        let tags: Vec<String> = match file.tags {
+            Some(Value::Sequence(items)) => items.iter().filter_map(scalar_text).collect(),
+            Some(Value::String(text)) => text.split(',').map(str::to_string).collect(),
+            tags => text("tags", tags).into_iter().collect(),
+        };
+        let tags = tags
+            .into_iter()
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+
+        let related_persona_key = persona.is_none() && related_persona.is_some();
+        let persona = match (persona, related_persona) {
+            (Some(persona), Some(related)) => {
+                extra.insert("related_persona".to_string(), Value::String(related));
+                Some(persona)
+            }
+            (persona, related) => persona.or(related),
+        };
+
+        Self {
+            tags,
+            status,
+            summary,
+            persona,
+            related_persona_key,
+            extra,
+        }
+    }
+}
+
+impl From<Frontmatter> for FrontmatterFile {
+    fn from(frontmatter: Frontmatter) -> Self {
+        let mut extra = frontmatter.extra;
+        // A value the app sets replaces one it couldn't read as text
+        let mut set = |key: &str, value: Option<String>| {
+            let value = value?;
+            extra.remove(key);
+            Some(Value::String(value))
+        };
+        let status = set("status", frontmatter.status);
+        let summary = set("summary", frontmatter.summary);
+        let (persona, related_persona) = if frontmatter.related_persona_key {
+            (None, set("related_persona", frontmatter.persona))
+        } else {
+            (set("persona", frontmatter.persona), None)
+        };
+
+        let tags = (!frontmatter.tags.is_empty()).then(|| {
+            extra.remove("tags");
+            Value::Sequence(frontmatter.tags.into_iter().map(Value::String).collect())
+        });
+
+        Self {
+            tags,
+            status,
+            summary,
+            persona,
+            related_persona,
+            extra,
+        }
+    }
+}
+
+impl Frontmatter {
+    /// Frontmatter of a document, empty if it has none or it isn't valid YAML
+    pub fn from_document(content: &str) -> Self {
+        split(content)
+            .0
+            .and_then(|yaml| serde_yaml::from_str::<Option<Self>>(yaml).ok())
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.status.is_none()
+            && self.summary.is_none()
+            && self.persona.is_none()
+            && self.extra.is_empty()
+    }
+}
+
+/// Split a document into its frontmatter YAML and body, the YAML being `None`
+/// unless the document starts with a closed `---` block
+fn split(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" || trimmed == "..." {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// A document without its frontmatter
+pub fn body(content: &str) -> &str {
+    split(content).1
+}
+
+/// Replace the frontmatter of a document, leaving the body exactly as it was
+///
+/// An empty frontmatter removes the block. Frontmatter that isn't valid YAML is
+/// never overwritten so nothing in it gets lost.
+pub fn with_frontmatter(content: &str, frontmatter: &Frontmatter) -> anyhow::Result<String> {
+    let (yaml, body) = split(content);
+    if let Some(yaml) = yaml {
+        if let Err(e) = serde_yaml::from_str::<Option<Frontmatter>>(yaml) {
+            anyhow::bail!("The document's frontmatter is not valid YAML: {}", e);
+        }
+    }
+
+    if frontmatter.is_empty() {
+        return Ok(body.to_string());
+    }
+    let yaml = serde_yaml::to_string(frontmatter)?;
+    Ok(format!("---\n{}---\n{}", yaml, body))
+}
+
+/// Knowledgebase entries shown for a chosen status and set of tags
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontmatterFilter {
+    pub status: Option<String>,
+    /// Entries must have every one of these tags
+    pub tags: Vec<String>,
+}
+
+impl FrontmatterFilter {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.tags.is_empty()
+    }
+
+    pub fn matches(&self, entry: &KnowledgebaseEntry) -> bool {
+        let frontmatter = &entry.frontmatter;
+        self.status
+            .as_ref()
+            .is_none_or(|status| frontmatter.status.as_ref() == Some(status))
+            && self.tags.iter().all(|tag| frontmatter.tags.contains(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    const DOCUMENT: &str =
+        "---\ntags: [review, berry]\nstatus: draft\nowner: geoff\n---\n# Code Review\n\nNotes.\n";
+
+    #[test]
+    fn test_parse_frontmatter() {
+        let frontmatter = Frontmatter::from_document(DOCUMENT);
+        assert_eq!(frontmatter.tags, vec!["review", "berry"]);
+        assert_eq!(frontmatter.status.as_deref(), Some("draft"));
+        assert_eq!(frontmatter.summary, None);
+        assert!(frontmatter.extra.contains_key("owner"));
+        assert_eq!(body(DOCUMENT), "# Code Review\n\nNotes.\n");
+
+        let text_tags =
+            Frontmatter::from_document("---\ntags: a, b\nrelated_persona: mentor\n---\n");
+        assert_eq!(text_tags.tags, vec!["a", "b"]);
+        assert_eq!(text_tags.persona.as_deref(), Some("mentor"));
+
+        // Without a closed block at the very top there is no frontmatter
+        assert!(Frontmatter::from_document("# Title\n---\nstatus: done\n---\n").is_empty());
+        assert!(Frontmatter::from_document("---\nstatus: done\n# Title\n").is_empty());
+        assert!(Frontmatter::from_document("---\n---\n# Empty\n").is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_keeps_body_and_unknown_keys() {
+        let mut frontmatter = Frontmatter::from_document(DOCUMENT);
+        frontmatter.status = Some("final".to_string());
+        frontmatter.summary = Some("Berry API review".to_string());
+
+        let rewritten = with_frontmatter(DOCUMENT, &frontmatter).unwrap();
+        assert_eq!(body(&rewritten), body(DOCUMENT));
+        assert_eq!(Frontmatter::from_document(&rewritten), frontmatter);
+        assert!(rewritten.contains("owner: geoff"));
+
+        let added = with_frontmatter("# Plain\n", &frontmatter).unwrap();
+        assert!(added.starts_with("---\n"));
+        assert_eq!(body(&added), "# Plain\n");
+
+        let removed = with_frontmatter(DOCUMENT, &Frontmatter::default()).unwrap();
+        assert_eq!(removed, "# Code Review\n\nNotes.\n");
+
+        let invalid = "---\ntags: [unclosed\n---\n# Broken\n";
+        assert!(with_frontmatter(invalid, &frontmatter).is_err());
+    }
+
+    #[test]
+    fn test_persona_is_written_under_the_key_it_was_read_from() {
+        let related = "---\nrelated_persona: mentor\n---\n# Notes\n";
+        let mut frontmatter = Frontmatter::from_document(related);
+        assert_eq!(frontmatter.persona.as_deref(), Some("mentor"));
+        frontmatter.persona = Some("assistant".to_string());
+
+        let rewritten = with_frontmatter(related, &frontmatter).unwrap();
+        assert!(rewritten.contains("related_persona: assistant"));
+        assert!(!rewritten.contains("\npersona:"));
+
+        let plain = "---\npersona: mentor\n---\n# Notes\n";
+        let rewritten = with_frontmatter(plain, &Frontmatter::from_document(plain)).unwrap();
+        assert!(rewritten.contains("\npersona: mentor"));
+        assert!(!rewritten.contains("related_persona"));
+
+        // With both keys the other one is kept as it was
+        let both = "---\npersona: mentor\nrelated_persona: assistant\n---\n";
+        let frontmatter = Frontmatter::from_document(both);
+        assert_eq!(frontmatter.persona.as_deref(), Some("mentor"));
+        let rewritten = with_frontmatter(both, &frontmatter).unwrap();
+        assert!(rewritten.contains("related_persona: assistant"));
+    }
+
+    #[test]
+    fn test_values_that_are_not_strings_are_read_leniently() {
+        let document =
+            "---\nstatus: 1\nsummary: false\ntags: [2026, true, review]\npersona: [a, b]\n---\n";
+        let frontmatter = Frontmatter::from_document(document);
+        assert_eq!(frontmatter.status.as_deref(), Some("1"));
+        assert_eq!(frontmatter.summary.as_deref(), Some("false"));
+        assert_eq!(frontmatter.tags, vec!["2026", "true", "review"]);
+        // A list can't be shown as a persona, so it is kept untouched instead
+        assert_eq!(frontmatter.persona, None);
+        assert!(frontmatter.extra.contains_key("persona"));
+
+        let rewritten = with_frontmatter(document, &frontmatter).unwrap();
+        assert_eq!(Frontmatter::from_document(&rewritten), frontmatter);
+
+        let mut edited = frontmatter;
+        edited.persona = Some("mentor".to_string());
+        let rewritten = with_frontmatter(document, &edited).unwrap();
+        assert_eq!(
+            Frontmatter::from_document(&rewritten).persona.as_deref(),
+            Some("mentor")
+        );
+        assert!(!Frontmatter::from_document(&rewritten)
+            .extra
+            .contains_key("persona"));
+    }
+
+    #[test]
+    fn test_filter_by_status_and_tags() {
+        let entry = KnowledgebaseEntry {
+            file_path: PathBuf::from("/kb/review.md"),
+            relative_path: PathBuf::from("review.md"),
+            name: "Code Review".to_string(),
+            modified_at: Utc::now(),
+            frontmatter: Frontmatter::from_document(DOCUMENT),
+        };
+
+        assert!(FrontmatterFilter::default().matches(&entry));
+        let filter = FrontmatterFilter {
+            status: Some("draft".to_string()),
+            tags: vec!["review".to_string()],
+        };
+        assert!(filter.matches(&entry));
+
+        let other_status = FrontmatterFilter {
+            status: Some("final".to_string()),
+            ..Default::default()
+        };
+        assert!(!other_status.matches(&entry));
+
+        let missing_tag = FrontmatterFilter {
+            tags: vec!["review".to_string(), "chroma".to_string()],
+            ..Default::default()
+        };
+        assert!(!missing_tag.matches(&entry));
+    }
+}
@@ -0,0 +1,379 @@
+use super::{load_entries, KnowledgebaseEntry};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Characters of context kept on each side of the first match in a snippet
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Weight of a query term matching the start of a longer word, relative to an exact match
+const PREFIX_WEIGHT: f64 = 0.5;
+
+/// Extra weight for terms that appear in the document's title
+const TITLE_WEIGHT: f64 = 2.0;
+
+/// Part of a document around the first match, with the matched words marked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet {
+    pub text: String,
+    /// Byte ranges of `text` that matched the query
+    pub highlights: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub kb_path: PathBuf,
+    pub entry: KnowledgebaseEntry,
+    pub score: f64,
+    pub snippet: Snippet,
+}
+
+#[derive(Debug)]
+struct IndexedDocument {
+    kb_path: PathBuf,
+    entry: KnowledgebaseEntry,
+    content: String,
+    /// Occurrences of each term in the content
+    terms: HashMap<String, usize>,
+    title_terms: Vec<String>,
+}
+
+/// Full-text index over knowledgebase Markdown files
+///
+/// Files are only re-read when their modified time changes, so `update` can be
+/// called before every search.
+#[derive(Debug, Default)]
+pub struct KnowledgebaseIndex {
+    documents: HashMap<PathBuf, IndexedDocument>,
+    /// Number of documents containing each term
+    document_frequency: HashMap<String, usize>,
+}
+
+impl KnowledgebaseIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring a knowledgebase up to date, returning how many files were added,
+    /// changed or removed
+    pub fn update(&mut self, kb_path: &Path) -> usize {
+        let entries = load_entries(kb_path);
+        let mut changed = 0;
+
+        let removed: Vec<PathBuf> = self
+            .documents
+            .iter()
+            .filter(|(path, doc)| {
+                doc.kb_path == kb_path && !entries.iter().any(|e| &e.file_path == *path)
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in removed {
+            self.remove(&path);
+            changed += 1;
+        }
+
+        for entry in entries {
+            let unchanged = self
+                .documents
+                .get(&entry.file_path)
+                .is_some_and(|doc| doc.entry.modified_at == entry.modified_at);
+            if unchanged {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&entry.file_path) else {
+                continue;
+            };
+            self.insert(kb_path, entry, content);
+            changed += 1;
+        }
+
+        changed
+    }
+
+    fn insert(&mut self, kb_path: &Path, entry: KnowledgebaseEntry, content: String) {
+        self.remove(&entry.file_path);
+
+        let mut terms: HashMap<String, usize> = HashMap::new();
+        for (_, word) in words(&content) {
+            *terms.entry(word).or_default() += 1;
+        }
+        for term in terms.keys() {
+            *self.document_frequency.entry(term.clone()).or_default() += 1;
+        }
+
+        let title_terms = words(&entry.name).into_iter().map(|(_, w)| w).collect();
+        self.documents.insert(
+            entry.file_path.clone(),
+            IndexedDocument {
+                kb_path: kb_path.to_path_buf(),
+                entry,
+                content,
+                terms,
+                title_terms,
+            },
+        );
+    }
+
+    fn remove(&mut self, path: &Path) {
+        let Some(doc) = self.documents.remove(path) else {
+            return;
+        };
+        for term in doc.terms.keys() {
+            if let Some(count) = self.document_frequency.get_mut(term) {
+                *count -= 1;
+                if *count == 0 {
+                    self.document_frequency.remove(term);
+                }
+            }
+        }
+    }
+
+    /// Documents matching any query term, best first, limited to one knowledgebase
+    /// when `scope` is given
+    ///
+    /// Each term also matches longer words starting with it so results show up
+    /// while a word is still being typed.
+    pub fn search(&self, query: &str, scope: Option<&Path>, limit: usize) -> Vec<SearchHit> {
+        let query_terms: Vec<String> = words(query).into_iter().map(|(_, w)| w).collect();
+        if query_terms.is_empty() {
+            return vec![];
+        }
+
+        let total = self.documents.len() as f64;
+        let mut hits: Vec<SearchHit> = self
+            .documents
+            .values()
+            .filter(|doc| scope.is_none_or(|kb_path| doc.kb_path == kb_path))
+            .filter_map(|doc| {
+                let mut score = 0.0;
+                let mut matched = 0;
+                for query_term in &query_terms {
+                    let term_score: f64 = doc
+                        .terms
+                        .iter()
+                        .filter_map(|(term, count)| {
+                            let weight = term_weight(query_term, term)?;
+                            let df = self.document_frequency.get(term).copied().unwrap_or(1);
+                            let idf = (1.0 + total / df as f64).ln();
+                            Some(weight * (1.0 + (*count as f64).ln()) * idf)
+                        })
+                        .sum();
+                    let in_title = doc
+                        .title_terms
+                        .iter()
+                        .any(|term| term_weight(query_term, term).is_some());
+                    if term_score > 0.0 || in_title {
+                        matched += 1;
+                        score += term_score + if in_title { TITLE_WEIGHT } else { 0.0 };
+                    }
+                }
+                if matched == 0 {
+                    return None;
+                }
+
+                // Documents matching every term outrank those matching a few often
+                let score = score * matched as f64 / query_terms.len() as f64;
+                Some(SearchHit {
+                    kb_path: doc.kb_path.clone(),
+                    entry: doc.entry.clone(),
+                    score,
+                    snippet: snippet(&doc.content, &query_terms),
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.entry.modified_at.cmp(&a.entry.modified_at))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// How well a query term matches an indexed word
+fn term_weight(query_term: &str, term: &str) -> Option<f64> {
+    if term == query_term {
+        Some(1.0)
+    } else if term.starts_with(query_term) {
+        Some(PREFIX_WEIGHT)
+    } else {
+        None
+    }
+}
+
+/// Lowercase words of a text with their byte ranges in the original
+fn words(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(s)) => {
+                words.push((s..index, text[s..index].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s..text.len(), text[s..].to_lowercase()));
+    }
+    words
+}
+
+/// Text around the first matching word, on a single line, with every match in it marked
+fn snippet(content: &str, query_terms: &[String]) -> Snippet {
+    let matches: Vec<Range<usize>> = words(content)
+        .into_iter()
+        .filter(|(_, word)| query_terms.iter().any(|q| term_weight(q, word).is_some()))
+        .map(|(range, _)| range)
+        .collect();
+
+    let Some(first) = matches.first() else {
+        let text: String = content.chars().take(SNIPPET_CONTEXT * 2).collect();
+        return Snippet {
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            highlights: vec![],
+        };
+    };
+
+    let start = content[..first.start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT - 1)
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+    let end = content[first.end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT)
+        .map(|(index, _)| first.end + index)
+        .unwrap_or(content.len());
+
+    // Flatten newlines so the snippet stays on one line, keeping byte offsets intact
+    let mut text: String = content[start..end]
+        .chars()
+        .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+        .collect();
+    let mut offset = 0;
+    if start > 0 {
+        text.insert_str(0, "...");
+        offset = 3;
+    }
+    if end < content.len() {
+        text.push_str("...");
+    }
+
+    let highlights = matches
+        .into_iter()
+        .filter(|range| range.start >= start && range.end <= end)
+        .map(|range| range.start - start + offset..range.end - start + offset)
+        .collect();
+
+    Snippet { text, highlights }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_words_keep_byte_ranges() {
+        let text = "# Café notes: Berry-API";
+        let words = words(text);
+        let found: Vec<&str> = words.iter().map(|(_, w)| w.as_str()).collect();
+        assert_eq!(found, vec!["café", "notes", "berry", "api"]);
+        assert_eq!(&text[words[0].0.clone()], "Café");
+    }
+
+    #[test]
+    fn test_search_ranks_and_scopes_results() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let mentor = temp_dir.path().join("mentor");
+        let assistant = temp_dir.path().join("assistant");
+        write(
+            &mentor,
+            "reviews.md",
+            "# Code Reviews\n\nReview pull requests daily. Reviews keep quality high.",
+        );
+        write(
+            &mentor,
+            "projects/berry.md",
+            "# Berry\n\nMemory server notes.",
+        );
+        write(
+            &assistant,
+            "errands.md",
+            "# Errands\n\nPick up the code review book.",
+        );
+
+        let mut index = KnowledgebaseIndex::new();
+        assert_eq!(index.update(&mentor), 2);
+        assert_eq!(index.update(&assistant), 1);
+        assert_eq!(index.documents.len(), 3);
+
+        let hits = index.search("review", None, 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].entry.name, "Code Reviews");
+        assert_eq!(hits[1].kb_path, assistant);
+
+        let scoped = index.search("review", Some(&assistant), 10);
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].entry.name, "Errands");
+
+        assert!(index.search("chroma", None, 10).is_empty());
+        assert!(index.search("  ", None, 10).is_empty());
+    }
+
+    #[test]
+    fn test_update_only_rereads_changed_files() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let kb = temp_dir.path();
+        write(kb, "a.md", "# A\n\nalpha");
+        write(kb, "b.md", "# B\n\nbeta");
+
+        let mut index = KnowledgebaseIndex::new();
+        assert_eq!(index.update(kb), 2);
+        assert_eq!(index.update(kb), 0);
+
+        write(kb, "a.md", "# A\n\ngamma");
+        let file = std::fs::File::options()
+            .write(true)
+            .open(kb.join("a.md"))
+            .unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        std::fs::remove_file(kb.join("b.md")).unwrap();
+
+        assert_eq!(index.update(kb), 2);
+        assert!(index.search("alpha", None, 10).is_empty());
+        assert!(index.search("beta", None, 10).is_empty());
+        assert_eq!(index.search("gamma", None, 10).len(), 1);
+    }
+
+    #[test]
+    fn test_snippet_highlights_matches() {
+        let content = format!("{}\nThe Berry server stores memories.", "x ".repeat(50));
+        let snippet = snippet(&content, &["berry".to_string(), "memor".to_string()]);
+
+        assert!(snippet.text.starts_with("..."));
+        assert!(!snippet.text.contains('\n'));
+        let highlighted: Vec<&str> = snippet
+            .highlights
+            .iter()
+            .map(|range| &snippet.text[range.clone()])
+            .collect();
+        assert_eq!(highlighted, vec!["Berry", "memories"]);
+    }
+}
@@ -1,63 +1,124 @@
+mod diff;
+mod files;
+mod frontmatter;
+mod index;
+mod watch;
+
+pub use diff::{diff_lines, DiffLine};
+pub use files::{
+    duplicate_file, move_file, new_document, rename_file, save_file, trash_file, TRASH_DIR,
+};
+pub use frontmatter::{body, with_frontmatter, Frontmatter, FrontmatterFilter};
+pub use index::{KnowledgebaseIndex, SearchHit};
+pub use watch::Snapshot;
+
 use chrono::{DateTime, Utc};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct KnowledgebaseEntry {
     pub file_path: PathBuf,
+    /// Path inside the knowledgebase directory, e.g. `projects/berry.md`
+    pub relative_path: PathBuf,
     pub name: String,
     pub modified_at: DateTime<Utc>,
+    pub frontmatter: Frontmatter,
+}
+
+impl KnowledgebaseEntry {
+    /// Folder containing the file relative to the knowledgebase, empty at the top level
+    pub fn folder(&self) -> &Path {
+        self.relative_path.parent().unwrap_or(Path::new(""))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct KnowledgebaseFile {
+    /// Knowledgebase the file belongs to, which saves must stay inside
+    pub kb_path: PathBuf,
     pub entry: KnowledgebaseEntry,
     pub content: String,
 }
 
+/// Load every Markdown file in a knowledgebase, including those in subfolders
 pub fn load_entries(kb_path: &Path) -> Vec<KnowledgebaseEntry> {
-    let mut entries = Vec::new();
+    let mut files = Vec::new();
+    collect_markdown_files(kb_path, &mut files);
+
+    let mut entries: Vec<KnowledgebaseEntry> = files
+        .iter()
+        .filter_map(|path| create_entry(kb_path, path))
+        .collect();
 
-    let Ok(read_dir) = std::fs::read_dir(kb_path) else {
-        return entries;
+    // Sort by modified date, newest first
+    entries.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+    entries
+}
+
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
     };
 
     for entry in read_dir.flatten() {
+        // Skip hidden files and folders such as `.git`
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
         let path = entry.path();
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "md") {
-            if let Some(kb_entry) = create_entry(&path) {
-                entries.push(kb_entry);
-            }
+        // Symlinks are not followed, so a folder link can't loop back on itself and
+        // a file link can't point saves outside the knowledgebase
+        if file_type.is_dir() {
+            collect_markdown_files(&path, files);
+        } else if file_type.is_file() && path.extension().map_or(false, |ext| ext == "md") {
+            files.push(path);
         }
     }
-
-    // Sort by modified date, newest first
-    entries.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
-
-    entries
 }
 
-fn create_entry(path: &Path) -> Option<KnowledgebaseEntry> {
+fn create_entry(kb_path: &Path, path: &Path) -> Option<KnowledgebaseEntry> {
     let metadata = std::fs::metadata(path).ok()?;
     let modified = metadata.modified().ok()?;
     let modified_at: DateTime<Utc> = modified.into();
 
+    let content = std::fs::read_to_string(path).ok();
+    let frontmatter = content
+        .as_deref()
+        .map(Frontmatter::from_document)
+        .unwrap_or_default();
+
     // Try to extract name from first # heading, fall back to filename
-    let name = extract_name_from_file(path).unwrap_or_else(|| {
-        path.file_stem()
-            .and_then(|s| s.to_str())
-            .map(|s| s.replace('-', " ").replace('_', " "))
-            .unwrap_or_else(|| "Unknown".to_string())
-    });
+    let name = content
+        .as_deref()
+        .and_then(|content| extract_name(body(content)))
+        .unwrap_or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.replace('-', " ").replace('_', " "))
+                .unwrap_or_else(|| "Unknown".to_string())
+        });
+
+    let relative_path = path
+        .strip_prefix(kb_path)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| PathBuf::from(path.file_name().unwrap_or_default()));
 
     Some(KnowledgebaseEntry {
         file_path: path.to_path_buf(),
+        relative_path,
         name,
         modified_at,
+        frontmatter,
     })
 }
 
-fn extract_name_from_file(path: &Path) -> Option<String> {
-    let content = std::fs::read_to_string(path).ok()?;
+fn extract_name(content: &str) -> Option<String> {
     for line in content.lines() {
         let trimmed = line.trim();
         if trimmed.starts_with("# ") {
@@ -67,14 +128,159 @@ fn extract_name_from_file(path: &Path) -> Option<String> {
     None
 }
 
-pub fn load_file(path: &Path) -> anyhow::Result<KnowledgebaseFile> {
+pub fn load_file(kb_path: &Path, path: &Path) -> anyhow::Result<KnowledgebaseFile> {
     let content = std::fs::read_to_string(path)?;
-    let entry = create_entry(path).ok_or_else(|| anyhow::anyhow!("Failed to create entry"))?;
+    let entry =
+        create_entry(kb_path, path).ok_or_else(|| anyhow::anyhow!("Failed to create entry"))?;
 
-    Ok(KnowledgebaseFile { entry, content })
+    Ok(KnowledgebaseFile {
+        kb_path: kb_path.to_path_buf(),
+        entry,
+        content,
+    })
+}
+
+/// How a file on disk differs from the content it was opened with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiskChange {
+    Modified(String),
+    Removed,
 }
 
-pub fn save_file(path: &Path, content: &str) -> anyhow::Result<()> {
-    std::fs::write(path, content)?;
-    Ok(())
+/// Check whether a file still holds `expected`, the content it was opened or
+/// last saved with
+pub fn changed_on_disk(path: &Path, expected: &str) -> anyhow::Result<Option<DiskChange>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) if content == expected => Ok(None),
+        Ok(content) => Ok(Some(DiskChange::Modified(content))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Some(DiskChange::Removed)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// File name stem derived from a title, lowercase words joined by dashes
+pub fn slugify(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        "untitled".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Create a new Markdown file named after a title, adding a number if the name is taken
+pub fn create_file(kb_path: &Path, title: &str, content: &str) -> anyhow::Result<PathBuf> {
+    create_unique(kb_path, &slugify(title), content)
+}
+
+/// Create `<stem>.md` in a directory, or `<stem>-2.md` and so on if the name is taken
+fn create_unique(dir: &Path, stem: &str, content: &str) -> anyhow::Result<PathBuf> {
+    for attempt in 1.. {
+        let file_name = if attempt == 1 {
+            format!("{}.md", stem)
+        } else {
+            format!("{}-{}.md", stem, attempt)
+        };
+        let path = dir.join(file_name);
+
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Code Review: 2026-02-04"), "code-review-2026-02-04");
+        assert_eq!(slugify("  Meeting   notes!  "), "meeting-notes");
+        assert_eq!(slugify("???"), "untitled");
+    }
+
+    #[test]
+    fn test_create_file_does_not_overwrite() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        let first = create_file(temp_dir.path(), "Meeting Notes", "# One\n").unwrap();
+        let second = create_file(temp_dir.path(), "Meeting Notes", "# Two\n").unwrap();
+
+        assert_eq!(first, temp_dir.path().join("meeting-notes.md"));
+        assert_eq!(second, temp_dir.path().join("meeting-notes-2.md"));
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "# One\n");
+
+        let entries = load_entries(temp_dir.path());
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn test_load_entries_walks_subfolders() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("projects").join("berry")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("index.md"), "# Index\n").unwrap();
+        std::fs::write(
+            root.join("projects").join("berry").join("api.md"),
+            "# API\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("projects").join("notes.txt"), "ignored").unwrap();
+        std::fs::write(root.join(".git").join("HEAD.md"), "ignored").unwrap();
+
+        let mut entries = load_entries(root);
+        entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+        let paths: Vec<&Path> = entries.iter().map(|e| e.relative_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("index.md"), Path::new("projects/berry/api.md")]
+        );
+        assert_eq!(entries[0].folder(), Path::new(""));
+        assert_eq!(entries[1].folder(), Path::new("projects/berry"));
+        assert_eq!(entries[1].name, "API");
+    }
+
+    #[test]
+    fn test_changed_on_disk() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("notes.md");
+        std::fs::write(&path, "# Notes\n").unwrap();
+
+        assert_eq!(changed_on_disk(&path, "# Notes\n").unwrap(), None);
+
+        std::fs::write(&path, "# Notes\n\nAdded by an agent\n").unwrap();
+        assert_eq!(
+            changed_on_disk(&path, "# Notes\n").unwrap(),
+            Some(DiskChange::Modified(
+                "# Notes\n\nAdded by an agent\n".to_string()
+            ))
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            changed_on_disk(&path, "# Notes\n").unwrap(),
+            Some(DiskChange::Removed)
+        );
+    }
 }
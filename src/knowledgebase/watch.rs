@@ -0,0 +1,71 @@
+use super::collect_markdown_files;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modified times of every Markdown file in a knowledgebase
+///
+/// Comparing two scans shows whether anything was added, changed or removed
+/// without reading the files themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Snapshot {
+    pub fn scan(kb_path: &Path) -> Self {
+        let mut paths = Vec::new();
+        collect_markdown_files(kb_path, &mut paths);
+
+        let files = paths
+            .into_iter()
+            .filter_map(|path| {
+                let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+                Some((path, modified))
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    /// Whether a file differs between two scans, including being added or removed
+    pub fn file_changed(&self, other: &Snapshot, path: &Path) -> bool {
+        self.files.get(path) != other.files.get(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_notices_changes() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let kb = temp_dir.path();
+        let notes = kb.join("notes.md");
+        std::fs::write(&notes, "# Notes\n").unwrap();
+        std::fs::write(kb.join("todo.txt"), "ignored").unwrap();
+
+        let first = Snapshot::scan(kb);
+        assert_eq!(first, Snapshot::scan(kb));
+
+        // Files the knowledgebase doesn't show are not watched
+        std::fs::write(kb.join("todo.txt"), "still ignored").unwrap();
+        assert_eq!(first, Snapshot::scan(kb));
+
+        let file = std::fs::File::options().write(true).open(&notes).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        let second = Snapshot::scan(kb);
+        assert_ne!(first, second);
+        assert!(first.file_changed(&second, &notes));
+
+        std::fs::write(kb.join("review.md"), "# Review\n").unwrap();
+        let third = Snapshot::scan(kb);
+        assert_ne!(second, third);
+        assert!(!second.file_changed(&third, &notes));
+        assert!(second.file_changed(&third, &kb.join("review.md")));
+    }
+}
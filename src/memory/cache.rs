@@ -0,0 +1,853 @@
+use super::{
+    BerryClient, BerryError, Memory, RememberRequest, SearchPage, SearchRequest,
+    UpdateMemoryRequest,
+};
+use crate::config::{data_dir, DEFAULT_PROFILE};
+use crate::knowledgebase::slugify;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Prefix for IDs of memories created while offline that Berry hasn't assigned an ID to yet
+const LOCAL_ID_PREFIX: &str = "local-";
+
+/// A change made while Berry was unreachable, replayed when it comes back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PendingOperation {
+    Remember {
+        local_id: String,
+        request: RememberRequest,
+    },
+    Update {
+        id: String,
+        request: UpdateMemoryRequest,
+        /// `updated_at` of the memory when it was edited, used to detect conflicts
+        base_updated_at: DateTime<Utc>,
+    },
+    Forget {
+        id: String,
+        as_actor: String,
+        base_updated_at: DateTime<Utc>,
+    },
+}
+
+impl PendingOperation {
+    pub fn memory_id(&self) -> &str {
+        match self {
+            PendingOperation::Remember { local_id, .. } => local_id,
+            PendingOperation::Update { id, .. } | PendingOperation::Forget { id, .. } => id,
+        }
+    }
+}
+
+/// Outcome of replaying pending operations against Berry
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub applied: usize,
+    /// IDs of memories changed on the server since they were edited offline
+    pub conflicts: Vec<String>,
+    /// Operations the server rejected, with the error message
+    pub failed: Vec<(String, String)>,
+    /// Operations that couldn't be sent because Berry is unreachable again
+    pub remaining: Vec<PendingOperation>,
+    /// Creates the server rejected, kept queued so memories written offline aren't lost
+    pub rejected: Vec<PendingOperation>,
+}
+
+impl SyncReport {
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("Synced {} offline change(s)", self.applied)];
+        if !self.conflicts.is_empty() {
+            parts.push(format!(
+                "{} skipped because they changed on the server",
+                self.conflicts.len()
+            ));
+        }
+        if !self.failed.is_empty() {
+            parts.push(format!("{} failed", self.failed.len()));
+        }
+        if !self.rejected.is_empty() {
+            parts.push(format!(
+                "{} new memory(s) kept offline to retry",
+                self.rejected.len()
+            ));
+        }
+        if !self.remaining.is_empty() {
+            parts.push(format!("{} still pending", self.remaining.len()));
+        }
+        parts.join(", ")
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    #[serde(default)]
+    memories: Vec<Memory>,
+    #[serde(default)]
+    pending: Vec<PendingOperation>,
+}
+
+/// On-disk cache of fetched memories and changes waiting to be synced
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    path: Option<PathBuf>,
+    memories: Vec<Memory>,
+    pending: Vec<PendingOperation>,
+}
+
+impl MemoryCache {
+    /// Cache location for a Berry profile inside the application data directory
+    pub fn profile_path(profile: &str) -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("cache").join(profile_file_name(profile)))
+    }
+
+    /// Whether two profile names would share a cache file
+    pub fn profiles_collide(a: &str, b: &str) -> bool {
+        profile_file_name(a) == profile_file_name(b)
+    }
+
+    /// Move the cache kept for a Berry profile to its new name
+    pub fn rename_profile(old: &str, new: &str) -> Result<(), std::io::Error> {
+        match (Self::profile_path(old), Self::profile_path(new)) {
+            (Some(from), Some(to)) => move_cache_file(&from, &to),
+            _ => Ok(()),
+        }
+    }
+
+    /// Set aside the cache of a deleted Berry profile, so a new profile given the
+    /// same name doesn't replay its queued changes against another server
+    pub fn delete_profile(profile: &str) -> Result<(), std::io::Error> {
+        match Self::profile_path(profile) {
+            Some(path) => archive_cache_file(&path).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Load the cache kept for a Berry profile
+    pub fn load_profile(profile: &str) -> Self {
+        match Self::profile_path(profile) {
+            Some(path) => Self::load(&path),
+            None => Self::default(),
+        }
+    }
+
+    /// Load the cache from a file, starting empty if it is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let file: CacheFile = if path.exists() {
+            std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| match serde_json::from_str(&content) {
+                    Ok(file) => Some(file),
+                    Err(e) => {
+                        eprintln!("Failed to parse memory cache {:?}: {}", path, e);
+                        None
+                    }
+                })
+                .unwrap_or_default()
+        } else {
+            CacheFile::default()
+        };
+
+        Self {
+            path: Some(path.to_path_buf()),
+            memories: file.memories,
+            pending: file.pending,
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = CacheFile {
+            memories: self.memories.clone(),
+            pending: self.pending.clone(),
+        };
+        let json = serde_json::to_string(&file)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        std::fs::write(path, json)
+    }
+
+    pub fn pending(&self) -> &[PendingOperation] {
+        &self.pending
+    }
+
+    /// Insert or replace memories fetched from the server
+    pub fn store(&mut self, memories: &[Memory]) {
+        for memory in memories {
+            match self.memories.iter_mut().find(|m| m.id == memory.id) {
+                Some(existing) => *existing = memory.clone(),
+                None => self.memories.push(memory.clone()),
+            }
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.memories.retain(|m| m.id != id);
+    }
+
+    /// Drop a memory Berry deleted, along with any change still queued for it as
+    /// replaying that would only fail
+    pub fn forgotten(&mut self, id: &str) {
+        self.pending.retain(|op| op.memory_id() != id);
+        self.remove(id);
+    }
+
+    /// Search cached memories the way Berry would, using plain text matching
+    pub fn search(&self, request: &SearchRequest) -> SearchPage {
+        let terms: Vec<String> = request
+            .query
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .collect();
+
+        let mut matches: Vec<&Memory> = self
+            .memories
+            .iter()
+            .filter(|m| m.is_visible_to(&request.as_actor))
+            .filter(|m| {
+                request
+                    .memory_type
+                    .as_ref()
+                    .is_none_or(|memory_type| &m.memory_type == memory_type)
+            })
+            .filter(|m| {
+                request
+                    .tags
+                    .as_ref()
+                    .is_none_or(|tags| tags.iter().all(|tag| m.tags.contains(tag)))
+            })
+            .filter(|m| {
+                request
+                    .created_by
+                    .as_ref()
+                    .is_none_or(|author| &m.created_by == author)
+            })
+            .filter(|m| {
+                let content = m.content.to_lowercase();
+                terms.iter().all(|term| {
+                    content.contains(term) || m.tags.iter().any(|t| t.to_lowercase() == *term)
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        let total = matches.len();
+        let offset = request.offset.unwrap_or(0) as usize;
+        let limit = request.limit.map(|l| l as usize).unwrap_or(total);
+
+        SearchPage {
+            memories: matches
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+            total,
+        }
+    }
+
+    /// Queue a new memory, adding a local copy so it can be browsed right away
+    pub fn queue_remember(&mut self, request: RememberRequest) -> Memory {
+        let now = Utc::now();
+        let base_id = format!("{}{}", LOCAL_ID_PREFIX, now.timestamp_millis());
+        let mut local_id = base_id.clone();
+        let mut suffix = 1;
+        while self.memories.iter().any(|m| m.id == local_id) {
+            local_id = format!("{}-{}", base_id, suffix);
+            suffix += 1;
+        }
+
+        let memory = Memory {
+            id: local_id.clone(),
+            content: request.content.clone(),
+            created_at: now,
+            updated_at: now,
+            created_by: request.created_by.clone(),
+            tags: request.tags.clone(),
+            memory_type: request.memory_type.clone(),
+            visibility: request.visibility.clone(),
+            shared_with: request.shared_with.clone(),
+        };
+
+        self.memories.push(memory.clone());
+        self.pending
+            .push(PendingOperation::Remember { local_id, request });
+
+        memory
+    }
+
+    /// Queue an update, applying it to the cached copy
+    pub fn queue_update(&mut self, id: &str, request: UpdateMemoryRequest) -> Option<Memory> {
+        let memory = self.memories.iter_mut().find(|m| m.id == id)?;
+        let base_updated_at = memory.updated_at;
+
+        apply_update(memory, &request);
+        memory.updated_at = Utc::now();
+        let updated = memory.clone();
+
+        if let Some(existing) = self.pending.iter_mut().find(|op| op.memory_id() == id) {
+            match existing {
+                // Not on the server yet, so fold the edit into the create
+                PendingOperation::Remember {
+                    request: create, ..
+                } => {
+                    create.content = updated.content.clone();
+                    create.memory_type = updated.memory_type.clone();
+                    create.tags = updated.tags.clone();
+                    create.visibility = updated.visibility.clone();
+                    create.shared_with = updated.shared_with.clone();
+                }
+                PendingOperation::Update {
+                    request: pending, ..
+                } => merge_update(pending, request),
+                PendingOperation::Forget { .. } => {}
+            }
+        } else {
+            self.pending.push(PendingOperation::Update {
+                id: id.to_string(),
+                request,
+                base_updated_at,
+            });
+        }
+
+        Some(updated)
+    }
+
+    /// Queue a delete, removing the cached copy
+    pub fn queue_forget(&mut self, id: &str, as_actor: &str) {
+        let Some(memory) = self.memories.iter().find(|m| m.id == id) else {
+            return;
+        };
+
+        // Offline edits keep the original timestamp so conflicts are still detected
+        let base_updated_at = self
+            .pending
+            .iter()
+            .find_map(|op| match op {
+                PendingOperation::Update {
+                    id: pending_id,
+                    base_updated_at,
+                    ..
+                } if pending_id == id => Some(*base_updated_at),
+                _ => None,
+            })
+            .unwrap_or(memory.updated_at);
+
+        let is_local = id.starts_with(LOCAL_ID_PREFIX);
+        self.pending.retain(|op| op.memory_id() != id);
+        self.remove(id);
+
+        if !is_local {
+            self.pending.push(PendingOperation::Forget {
+                id: id.to_string(),
+                as_actor: as_actor.to_string(),
+                base_updated_at,
+            });
+        }
+    }
+
+    /// Record the result of replaying the first `replayed` pending operations,
+    /// dropping local copies of memories that now exist on the server
+    pub fn finish_sync(&mut self, replayed: usize, report: &SyncReport) {
+        let queued_since = self.pending.split_off(replayed.min(self.pending.len()));
+        self.pending = report.rejected.clone();
+        self.pending.extend(report.remaining.iter().cloned());
+        self.pending.extend(queued_since);
+
+        self.memories
+            .retain(|m| !m.id.starts_with(LOCAL_ID_PREFIX) || report_keeps(report, &m.id));
+    }
+}
+
+/// The default profile keeps the file used before profiles existed
+fn profile_file_name(profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        "memories.json".to_string()
+    } else {
+        format!("memories-{}.json", slugify(profile))
+    }
+}
+
+/// Move a cache file, never replacing one that is already there
+fn move_cache_file(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    if from == to || !from.exists() {
+        return Ok(());
+    }
+    if to.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    std::fs::rename(from, to)
+}
+
+/// Move a cache file into the `deleted` folder next to it, stamped so repeated
+/// deletes don't collide, returning where it went
+fn archive_cache_file(path: &Path) -> Result<Option<PathBuf>, std::io::Error> {
+    let (Some(dir), Some(stem)) = (path.parent(), path.file_stem()) else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let archive = dir.join("deleted");
+    std::fs::create_dir_all(&archive)?;
+    let stamp = Utc::now().format("%Y%m%d-%H%M%S%.3f");
+    let target = archive.join(format!("{}-{}.json", stem.to_string_lossy(), stamp));
+    std::fs::rename(path, &target)?;
+    Ok(Some(target))
+}
+
+fn report_keeps(report: &SyncReport, id: &str) -> bool {
+    report
+        .remaining
+        .iter()
+        .chain(&report.rejected)
+        .any(|op| op.memory_id() == id)
+}
+
+fn apply_update(memory: &mut Memory, request: &UpdateMemoryRequest) {
+    if let Some(content) = &request.content {
+        memory.content = content.clone();
+    }
+    if let Some(memory_type) = &request.memory_type {
+        memory.memory_type = memory_type.clone();
+    }
+    if let Some(tags) = &request.tags {
+        memory.tags = tags.clone();
+    }
+    if let Some(visibility) = &request.visibility {
+        memory.visibility = visibility.clone();
+    }
+    if let Some(shared_with) = &request.shared_with {
+        memory.shared_with = shared_with.clone();
+    }
+}
+
+fn merge_update(pending: &mut UpdateMemoryRequest, request: UpdateMemoryRequest) {
+    pending.content = request.content.or(pending.content.take());
+    pending.memory_type = request.memory_type.or(pending.memory_type.take());
+    pending.tags = request.tags.or(pending.tags.take());
+    pending.visibility = request.visibility.or(pending.visibility.take());
+    pending.shared_with = request.shared_with.or(pending.shared_with.take());
+}
+
+/// Replay queued operations in order, stopping if Berry becomes unreachable again
+/// or rejects the credentials, so the rest can be retried later
+pub async fn replay(client: &BerryClient, operations: Vec<PendingOperation>) -> SyncReport {
+    let mut report = SyncReport::default();
+    let mut operations = operations.into_iter();
+
+    while let Some(operation) = operations.next() {
+        let result = replay_operation(client, &operation).await;
+        match result {
+            Ok(true) => report.applied += 1,
+            Ok(false) => report.conflicts.push(operation.memory_id().to_string()),
+            Err(e) if e.is_unreachable() || e.is_auth_failure() => {
+                report.remaining.push(operation);
+                report.remaining.extend(operations);
+                break;
+            }
+            Err(e) => {
+                report
+                    .failed
+                    .push((operation.memory_id().to_string(), e.to_string()));
+                if matches!(operation, PendingOperation::Remember { .. }) {
+                    report.rejected.push(operation);
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Returns `Ok(false)` when the memory changed on the server since it was edited
+async fn replay_operation(
+    client: &BerryClient,
+    operation: &PendingOperation,
+) -> Result<bool, BerryError> {
+    match operation {
+        PendingOperation::Remember { request, .. } => {
+            client.remember(request.clone()).await?;
+            Ok(true)
+        }
+        PendingOperation::Update {
+            id,
+            request,
+            base_updated_at,
+        } => {
+            let remote = client.get_memory(id, &request.as_actor).await?;
+            if remote.updated_at > *base_updated_at {
+                return Ok(false);
+            }
+            client.update_memory(id, request.clone()).await?;
+            Ok(true)
+        }
+        PendingOperation::Forget {
+            id,
+            as_actor,
+            base_updated_at,
+        } => {
+            let remote = client.get_memory(id, as_actor).await?;
+            if remote.updated_at > *base_updated_at {
+                return Ok(false);
+            }
+            client.forget(id, as_actor).await?;
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::mock::{block_on, fixture_memory, MemoryBuilder, MockBerryServer};
+    use crate::memory::{MemoryType, Visibility};
+    use tempfile::TempDir;
+
+    fn remember_request(content: &str) -> RememberRequest {
+        RememberRequest {
+            content: content.to_string(),
+            created_by: "persona-ui".to_string(),
+            memory_type: MemoryType::Request,
+            tags: vec!["assignment".to_string()],
+            visibility: Visibility::Public,
+            shared_with: vec![],
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("cache").join("memories.json");
+
+        let mut cache = MemoryCache::load(&path);
+        cache.store(&[MemoryBuilder::new("mem-1", "Hello").build()]);
+        cache.queue_remember(remember_request("Queued"));
+        cache.save().expect("Failed to save cache");
+
+        let loaded = MemoryCache::load(&path);
+        assert_eq!(loaded.memories.len(), 2);
+        assert_eq!(loaded.pending().len(), 1);
+    }
+
+    #[test]
+    fn test_profile_file_name() {
+        assert_eq!(profile_file_name(DEFAULT_PROFILE), "memories.json");
+        assert_eq!(profile_file_name("Work Berry"), "memories-work-berry.json");
+        assert!(MemoryCache::profiles_collide("Work Berry", "work-berry"));
+        assert!(!MemoryCache::profiles_collide("work", "personal"));
+    }
+
+    #[test]
+    fn test_move_cache_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let from = temp_dir.path().join(profile_file_name("work"));
+        let to = temp_dir.path().join(profile_file_name("office"));
+
+        // Nothing cached yet is nothing to move
+        move_cache_file(&from, &to).expect("Failed to move missing cache");
+        assert!(!to.exists());
+
+        let mut cache = MemoryCache::load(&from);
+        cache.queue_remember(remember_request("Queued"));
+        cache.save().expect("Failed to save cache");
+        move_cache_file(&from, &to).expect("Failed to move cache");
+        assert!(!from.exists());
+        assert_eq!(MemoryCache::load(&to).pending().len(), 1);
+
+        std::fs::write(&from, "{}").unwrap();
+        assert!(move_cache_file(&from, &to).is_err());
+        assert_eq!(MemoryCache::load(&to).pending().len(), 1);
+    }
+
+    #[test]
+    fn test_archive_cache_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join(profile_file_name("profile-2"));
+        assert_eq!(archive_cache_file(&path).unwrap(), None);
+
+        let mut cache = MemoryCache::load(&path);
+        cache.queue_remember(remember_request("Queued"));
+        cache.save().expect("Failed to save cache");
+
+        let archived = archive_cache_file(&path)
+            .expect("Failed to archive cache")
+            .expect("Cache was not archived");
+        assert!(archived.starts_with(temp_dir.path().join("deleted")));
+        assert_eq!(MemoryCache::load(&archived).pending().len(), 1);
+        // A profile created with the same name starts with nothing queued
+        assert!(MemoryCache::load(&path).pending().is_empty());
+    }
+
+    #[test]
+    fn test_search_filters_by_query_and_visibility() {
+        let mut cache = MemoryCache::default();
+        cache.store(&[
+            MemoryBuilder::new("mem-1", "Review the backlog").build(),
+            MemoryBuilder::new("mem-2", "Private backlog notes")
+                .visibility(Visibility::Private)
+                .build(),
+            MemoryBuilder::new("mem-3", "Unrelated").build(),
+        ]);
+
+        let page = cache.search(&SearchRequest {
+            query: "backlog".to_string(),
+            as_actor: "persona-ui".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(page.total, 1);
+        assert_eq!(page.memories[0].id, "mem-1");
+
+        let page = cache.search(&SearchRequest {
+            query: "backlog".to_string(),
+            as_actor: "staff-mentor".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(page.total, 2);
+    }
+
+    #[test]
+    fn test_search_applies_offset_and_limit() {
+        let mut cache = MemoryCache::default();
+        cache.store(&[
+            MemoryBuilder::new("mem-1", "One").created_by("a").build(),
+            MemoryBuilder::new("mem-2", "Two").created_by("a").build(),
+            MemoryBuilder::new("mem-3", "Three").created_by("a").build(),
+        ]);
+
+        let page = cache.search(&SearchRequest {
+            as_actor: "a".to_string(),
+            limit: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(page.total, 3);
+        assert_eq!(page.memories.len(), 1);
+    }
+
+    #[test]
+    fn test_update_of_local_memory_folds_into_remember() {
+        let mut cache = MemoryCache::default();
+        let local = cache.queue_remember(remember_request("Draft"));
+
+        cache.queue_update(
+            &local.id,
+            UpdateMemoryRequest {
+                as_actor: "persona-ui".to_string(),
+                content: Some("Final".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(cache.pending().len(), 1);
+        match &cache.pending()[0] {
+            PendingOperation::Remember { request, .. } => assert_eq!(request.content, "Final"),
+            other => panic!("Expected remember, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_forget_keeps_original_base_timestamp() {
+        let mut cache = MemoryCache::default();
+        let original = MemoryBuilder::new("mem-1", "Hello").created_by("a").build();
+        cache.store(std::slice::from_ref(&original));
+
+        cache.queue_update(
+            "mem-1",
+            UpdateMemoryRequest {
+                as_actor: "a".to_string(),
+                content: Some("Edited".to_string()),
+                ..Default::default()
+            },
+        );
+        cache.queue_forget("mem-1", "a");
+
+        assert_eq!(cache.pending().len(), 1);
+        match &cache.pending()[0] {
+            PendingOperation::Forget {
+                base_updated_at, ..
+            } => assert_eq!(*base_updated_at, original.updated_at),
+            other => panic!("Expected forget, got {:?}", other),
+        }
+        assert!(cache.memories.is_empty());
+    }
+
+    #[test]
+    fn test_forget_of_local_memory_drops_the_remember() {
+        let mut cache = MemoryCache::default();
+        let local = cache.queue_remember(remember_request("Draft"));
+
+        cache.queue_forget(&local.id, "persona-ui");
+
+        assert!(cache.pending().is_empty());
+        assert!(cache.memories.is_empty());
+    }
+
+    #[test]
+    fn test_finish_sync_requeues_remaining_operations() {
+        let mut cache = MemoryCache::default();
+        let local = cache.queue_remember(remember_request("Draft"));
+        let operations = cache.pending().to_vec();
+        cache.queue_remember(remember_request("Queued during sync"));
+
+        cache.finish_sync(
+            operations.len(),
+            &SyncReport {
+                remaining: operations,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(cache.pending().len(), 2);
+        assert!(cache.memories.iter().any(|m| m.id == local.id));
+    }
+
+    #[test]
+    fn test_online_forget_removes_the_cached_copy() {
+        let server = MockBerryServer::start(vec![
+            fixture_memory("mem-1", "Deleted online"),
+            fixture_memory("mem-2", "Kept"),
+        ]);
+        let client = BerryClient::new(server.url());
+
+        let mut cache = MemoryCache::default();
+        let request = SearchRequest {
+            as_actor: "persona-ui".to_string(),
+            ..Default::default()
+        };
+        let page = block_on(client.search(request.clone())).expect("Search failed");
+        cache.store(&page.memories);
+        cache.queue_update(
+            "mem-1",
+            UpdateMemoryRequest {
+                as_actor: "persona-ui".to_string(),
+                content: Some("Edited offline".to_string()),
+                ..Default::default()
+            },
+        );
+
+        block_on(client.forget("mem-1", "persona-ui")).expect("Forget failed");
+        cache.forgotten("mem-1");
+
+        let ids: Vec<String> = cache
+            .search(&request)
+            .memories
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec!["mem-2"]);
+        assert!(cache.pending().is_empty());
+    }
+
+    #[test]
+    fn test_replay_applies_changes_and_detects_conflicts() {
+        let server = MockBerryServer::start(vec![
+            fixture_memory("mem-1", "Untouched on the server"),
+            fixture_memory("mem-2", "Edited on the server"),
+        ]);
+        let client = BerryClient::new(server.url());
+
+        let mut cache = MemoryCache::default();
+        let page = block_on(client.search(SearchRequest {
+            as_actor: "persona-ui".to_string(),
+            ..Default::default()
+        }))
+        .expect("Search failed");
+        cache.store(&page.memories);
+
+        for id in ["mem-1", "mem-2"] {
+            cache.queue_update(
+                id,
+                UpdateMemoryRequest {
+                    as_actor: "persona-ui".to_string(),
+                    content: Some("Edited offline".to_string()),
+                    ..Default::default()
+                },
+            );
+        }
+        cache.queue_remember(remember_request("Created offline"));
+        server.set_field(
+            "mem-2",
+            "updated_at",
+            serde_json::json!("2026-02-01T00:00:00Z"),
+        );
+
+        let operations = cache.pending().to_vec();
+        let report = block_on(replay(&client, operations.clone()));
+        cache.finish_sync(operations.len(), &report);
+
+        assert_eq!(report.applied, 2);
+        assert_eq!(report.conflicts, vec!["mem-2"]);
+        assert!(cache.pending().is_empty());
+        assert!(!cache
+            .memories
+            .iter()
+            .any(|m| m.id.starts_with(LOCAL_ID_PREFIX)));
+
+        let contents: Vec<String> = server
+            .memories()
+            .iter()
+            .map(|m| m["content"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            contents,
+            vec!["Edited offline", "Edited on the server", "Created offline"]
+        );
+    }
+
+    #[test]
+    fn test_rejected_creates_stay_pending() {
+        let server = MockBerryServer::start(vec![]);
+        server.fail_next(400, "Content too long");
+        let client = BerryClient::new(server.url());
+
+        let mut cache = MemoryCache::default();
+        let rejected = cache.queue_remember(remember_request("Rejected"));
+        cache.queue_remember(remember_request("Accepted"));
+
+        let operations = cache.pending().to_vec();
+        let report = block_on(replay(&client, operations.clone()));
+        cache.finish_sync(operations.len(), &report);
+
+        assert_eq!(report.applied, 1);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(cache.pending().len(), 1);
+        assert_eq!(cache.pending()[0].memory_id(), rejected.id);
+        let local: Vec<&str> = cache
+            .memories
+            .iter()
+            .filter(|m| m.id.starts_with(LOCAL_ID_PREFIX))
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(local, vec!["Rejected"]);
+    }
+
+    #[test]
+    fn test_replay_keeps_operations_when_unreachable() {
+        let server = MockBerryServer::start(vec![]);
+        let client = BerryClient::new(server.url());
+        drop(server);
+
+        let mut cache = MemoryCache::default();
+        cache.queue_remember(remember_request("First"));
+        cache.queue_remember(remember_request("Second"));
+
+        let report = block_on(replay(&client, cache.pending().to_vec()));
+
+        assert_eq!(report.applied, 0);
+        assert_eq!(report.remaining.len(), 2);
+    }
+}
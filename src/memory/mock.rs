@@ -0,0 +1,371 @@
+//! Stand-in Berry server for tests, serving fixture memories over plain HTTP
+
+use super::{Memory, MemoryType, RawMemory, Visibility};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    /// Header names lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    /// Memories in the raw snake_case shape Berry returns
+    memories: Vec<Value>,
+    /// Error responses returned, in order, before any normal handling
+    failures: VecDeque<(u16, String)>,
+    requests: Vec<RecordedRequest>,
+    /// Header every request must carry, answered with 401 otherwise
+    required_header: Option<(String, String)>,
+    next_id: usize,
+}
+
+/// Berry HTTP server running on a background thread for the lifetime of a test
+pub struct MockBerryServer {
+    url: String,
+    state: Arc<Mutex<MockState>>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockBerryServer {
+    pub fn start(memories: Vec<Value>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(MockState {
+            memories,
+            ..Default::default()
+        }));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread_state = state.clone();
+        let thread_stopped = stopped.clone();
+        let thread = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if thread_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    handle_connection(stream, &thread_state);
+                }
+            }
+        });
+
+        Self {
+            url,
+            state,
+            stopped,
+            thread: Some(thread),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Respond to the next request with the given status and body
+    pub fn fail_next(&self, status: u16, body: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .failures
+            .push_back((status, body.to_string()));
+    }
+
+    /// Reject requests that don't send the given header value
+    pub fn require_header(&self, name: &str, value: &str) {
+        self.state.lock().unwrap().required_header = Some((name.to_string(), value.to_string()));
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn memories(&self) -> Vec<Value> {
+        self.state.lock().unwrap().memories.clone()
+    }
+
+    /// Replace a field on a stored memory, e.g. to simulate an edit made elsewhere
+    pub fn set_field(&self, id: &str, field: &str, value: Value) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(memory) = state.memories.iter_mut().find(|m| m["id"] == id) {
+            memory[field] = value;
+        }
+    }
+}
+
+impl Drop for MockBerryServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the stop flag
+        let _ = TcpStream::connect(self.url.trim_start_matches("http://"));
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// A memory in the shape Berry returns it
+pub fn fixture_memory(id: &str, content: &str) -> Value {
+    json!({
+        "id": id,
+        "content": content,
+        "type": "information",
+        "tags": ["fixture"],
+        "created_by": "staff-mentor",
+        "created_at": "2026-01-13T09:33:09Z",
+        "updated_at": "2026-01-13T09:33:09Z",
+        "visibility": "public",
+        "shared_with": []
+    })
+}
+
+/// A `Memory` for tests that don't need a server, starting from the same values
+/// as `fixture_memory`
+pub struct MemoryBuilder(Memory);
+
+impl MemoryBuilder {
+    pub fn new(id: &str, content: &str) -> Self {
+        let raw: RawMemory = serde_json::from_value(fixture_memory(id, content))
+            .expect("Fixture memory should deserialize");
+        Self(raw.into())
+    }
+
+    pub fn created_by(mut self, actor: &str) -> Self {
+        self.0.created_by = actor.to_string();
+        self
+    }
+
+    /// Sets both the created and updated times
+    pub fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.0.created_at = created_at;
+        self.0.updated_at = created_at;
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.0.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        self
+    }
+
+    pub fn memory_type(mut self, memory_type: MemoryType) -> Self {
+        self.0.memory_type = memory_type;
+        self
+    }
+
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.0.visibility = visibility;
+        self
+    }
+
+    pub fn shared_with(mut self, actors: &[&str]) -> Self {
+        self.0.visibility = Visibility::Shared;
+        self.0.shared_with = actors.iter().map(|actor| actor.to_string()).collect();
+        self
+    }
+
+    pub fn build(self) -> Memory {
+        self.0
+    }
+}
+
+/// Run a future to completion on a fresh runtime
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime")
+        .block_on(future)
+}
+
+fn handle_connection(mut stream: TcpStream, state: &Mutex<MockState>) {
+    let Some(request) = read_request(&stream) else {
+        return;
+    };
+
+    let (status, body) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request.clone());
+        let authorized = state
+            .required_header
+            .as_ref()
+            .is_none_or(|(name, value)| request.header(name) == Some(value.as_str()));
+        match state.failures.pop_front() {
+            Some(failure) => failure,
+            None if !authorized => (401, json!({ "error": "Unauthorized" }).to_string()),
+            None => route(&mut state, &request),
+        }
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn read_request(stream: &TcpStream) -> Option<RecordedRequest> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut content_length = 0;
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(RecordedRequest {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+fn route(state: &mut MockState, request: &RecordedRequest) -> (u16, String) {
+    let body: Value = serde_json::from_str(&request.body).unwrap_or(Value::Null);
+    let memory_id = request.path.strip_prefix("/v1/memory/");
+
+    match (request.method.as_str(), request.path.as_str(), memory_id) {
+        ("GET", "/health", _) => (200, json!({ "status": "ok" }).to_string()),
+        ("POST", "/v1/search", _) => (200, search(state, &body).to_string()),
+        ("POST", "/v1/memory", _) => {
+            state.next_id += 1;
+            let now = Utc::now().to_rfc3339();
+            let memory = json!({
+                "id": format!("mock-{}", state.next_id),
+                "content": body["content"],
+                "type": body["type"],
+                "tags": body.get("tags").cloned().unwrap_or(json!([])),
+                "created_by": body["createdBy"],
+                "created_at": now,
+                "updated_at": now,
+                "visibility": body["visibility"],
+                "shared_with": body.get("sharedWith").cloned().unwrap_or(json!([])),
+            });
+            state.memories.push(memory.clone());
+            (200, memory.to_string())
+        }
+        (method, _, Some(id)) => {
+            let Some(index) = state.memories.iter().position(|m| m["id"] == id) else {
+                return (404, json!({ "error": "Memory not found" }).to_string());
+            };
+            match method {
+                "GET" => (200, state.memories[index].to_string()),
+                "PATCH" => {
+                    let memory = &mut state.memories[index];
+                    for (field, raw_field) in [
+                        ("content", "content"),
+                        ("type", "type"),
+                        ("tags", "tags"),
+                        ("visibility", "visibility"),
+                        ("sharedWith", "shared_with"),
+                    ] {
+                        if let Some(value) = body.get(field) {
+                            memory[raw_field] = value.clone();
+                        }
+                    }
+                    memory["updated_at"] = json!(Utc::now().to_rfc3339());
+                    (200, memory.to_string())
+                }
+                "DELETE" => {
+                    state.memories.remove(index);
+                    (200, json!({ "success": true }).to_string())
+                }
+                _ => (405, String::new()),
+            }
+        }
+        _ => (404, json!({ "error": "Not found" }).to_string()),
+    }
+}
+
+/// Plain substring search with paging, standing in for the vector search
+///
+/// Tag, type and author filters are applied like Berry does, a memory needing
+/// every requested tag.
+fn search(state: &MockState, body: &Value) -> Value {
+    let query = body["query"].as_str().unwrap_or_default().to_lowercase();
+    let tags: Vec<&Value> = body["tags"].as_array().into_iter().flatten().collect();
+    let matches: Vec<&Value> = state
+        .memories
+        .iter()
+        .filter(|m| {
+            m["content"]
+                .as_str()
+                .unwrap_or_default()
+                .to_lowercase()
+                .contains(&query)
+        })
+        .filter(|m| {
+            let memory_tags = m["tags"].as_array();
+            tags.iter()
+                .all(|tag| memory_tags.is_some_and(|memory_tags| memory_tags.contains(tag)))
+        })
+        .filter(|m| {
+            body.get("type")
+                .is_none_or(|memory_type| &m["type"] == memory_type)
+        })
+        .filter(|m| {
+            body.get("createdBy")
+                .is_none_or(|author| &m["created_by"] == author)
+        })
+        .collect();
+
+    let offset = body["offset"].as_u64().unwrap_or(0) as usize;
+    let limit = body["limit"]
+        .as_u64()
+        .map(|l| l as usize)
+        .unwrap_or(matches.len());
+
+    json!({
+        "success": true,
+        "memories": matches.iter().skip(offset).take(limit).collect::<Vec<_>>(),
+        "total": matches.len(),
+    })
+}
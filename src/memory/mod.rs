@@ -1,7 +1,15 @@
+mod cache;
+#[cfg(test)]
+pub(crate) mod mock;
+pub mod transfer;
 mod types;
 
+pub use cache::*;
 pub use types::*;
 
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use serde::de::DeserializeOwned;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error};
 
@@ -13,12 +21,72 @@ pub enum BerryError {
     Server { status: u16, message: String },
     #[error("Parse error: {0}")]
     Parse(String),
+    #[error("Authentication failed: {status} - {message}")]
+    Auth { status: u16, message: String },
+    /// The configured credentials can't be sent, so no request was made
+    #[error("Invalid Berry credentials: {0}")]
+    InvalidAuth(String),
 }
 
+impl BerryError {
+    /// Whether the request never reached the server, as opposed to being rejected by it
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, BerryError::Http(_))
+    }
+
+    /// Whether the credentials were rejected by the server or couldn't be used at all
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(self, BerryError::Auth { .. } | BerryError::InvalidAuth(_))
+    }
+}
+
+/// Credentials sent with every request to Berry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BerryAuth {
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+    /// Token sent as the value of a custom header
+    Header { name: String, token: String },
+}
+
+impl BerryAuth {
+    /// Check that the credentials can be sent as a header
+    pub fn validate(&self) -> Result<(), BerryError> {
+        self.headers().map(|_| ())
+    }
+
+    fn headers(&self) -> Result<HeaderMap, BerryError> {
+        let mut headers = HeaderMap::new();
+        let (name, value) = match self {
+            BerryAuth::None => return Ok(headers),
+            BerryAuth::Bearer(token) => (AUTHORIZATION, format!("Bearer {}", token)),
+            BerryAuth::Header { name, token } => (
+                HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                    BerryError::InvalidAuth(format!("header name {:?}: {}", name, e))
+                })?,
+                token.clone(),
+            ),
+        };
+        let mut value = HeaderValue::from_str(&value)
+            .map_err(|e| BerryError::InvalidAuth(format!("token: {}", e)))?;
+        value.set_sensitive(true);
+        headers.insert(name, value);
+        Ok(headers)
+    }
+}
+
+/// How long a health check waits before the server is considered offline
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct BerryClient {
     base_url: String,
     client: reqwest::Client,
+    /// Why the configured credentials couldn't be used, returned by every request
+    /// rather than sending it without them
+    auth_error: Option<String>,
 }
 
 impl BerryClient {
@@ -26,60 +94,369 @@ impl BerryClient {
         Self {
             base_url: base_url.into(),
             client: reqwest::Client::new(),
+            auth_error: None,
         }
     }
 
+    /// Send the given credentials with every request
+    pub fn with_auth(mut self, auth: &BerryAuth) -> Self {
+        let client = auth.headers().and_then(|headers| {
+            reqwest::Client::builder()
+                .default_headers(headers)
+                .build()
+                .map_err(|e| BerryError::InvalidAuth(e.to_string()))
+        });
+        match client {
+            Ok(client) => self.client = client,
+            Err(e) => {
+                error!("Failed to configure Berry auth: {}", e);
+                self.auth_error = Some(match e {
+                    BerryError::InvalidAuth(message) => message,
+                    other => other.to_string(),
+                });
+            }
+        }
+        self
+    }
+
+    /// The HTTP client, or the reason the configured credentials can't be sent
+    fn http(&self) -> Result<&reqwest::Client, BerryError> {
+        match &self.auth_error {
+            Some(message) => Err(BerryError::InvalidAuth(message.clone())),
+            None => Ok(&self.client),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Check that the server is reachable, returning the round trip latency
+    pub async fn health(&self) -> Result<Duration, BerryError> {
+        let url = format!("{}/health", self.base_url);
+        debug!("GET {}", url);
+
+        let started = Instant::now();
+        let response = self
+            .http()?
+            .get(&url)
+            .timeout(HEALTH_TIMEOUT)
+            .send()
+            .await?;
+
+        read_body(response).await?;
+        Ok(started.elapsed())
+    }
+
     pub async fn get_memory(&self, id: &str, as_actor: &str) -> Result<Memory, BerryError> {
         let url = format!("{}/v1/memory/{}", self.base_url, id);
         debug!("GET {}", url);
 
         let response = self
-            .client
+            .http()?
             .get(&url)
             .query(&[("asActor", as_actor)])
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
-            error!("Server error: {} - {}", status, message);
-            return Err(BerryError::Server { status, message });
-        }
-
-        let body = response.text().await?;
-        debug!("Response body: {}", &body[..body.len().min(500)]);
-
-        let result: RawMemory = serde_json::from_str(&body).map_err(|e| {
-            error!("Failed to parse response: {}", e);
-            error!("Response body was: {}", &body[..body.len().min(1000)]);
-            BerryError::Parse(e.to_string())
-        })?;
+        let body = read_body(response).await?;
+        let result: RawMemory = parse_body(&body)?;
         Ok(result.into())
     }
 
-    pub async fn search(&self, request: SearchRequest) -> Result<Vec<Memory>, BerryError> {
+    pub async fn search(&self, request: SearchRequest) -> Result<SearchPage, BerryError> {
         let url = format!("{}/v1/search", self.base_url);
         debug!("POST {} with request: {:?}", url, request);
 
-        let response = self.client.post(&url).json(&request).send().await?;
+        let response = self.http()?.post(&url).json(&request).send().await?;
+
+        let body = read_body(response).await?;
+        let result: SearchResponse = parse_body(&body)?;
+
+        Ok(SearchPage {
+            memories: result.memories.into_iter().map(Memory::from).collect(),
+            total: result.total,
+        })
+    }
+
+    pub async fn remember(&self, request: RememberRequest) -> Result<Memory, BerryError> {
+        let url = format!("{}/v1/memory", self.base_url);
+        debug!("POST {} with request: {:?}", url, request);
+
+        let response = self.http()?.post(&url).json(&request).send().await?;
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
-            error!("Server error: {} - {}", status, message);
-            return Err(BerryError::Server { status, message });
+        let body = read_body(response).await?;
+        let result: RawMemory = parse_body(&body)?;
+        Ok(result.into())
+    }
+
+    pub async fn update_memory(
+        &self,
+        id: &str,
+        request: UpdateMemoryRequest,
+    ) -> Result<Memory, BerryError> {
+        let url = format!("{}/v1/memory/{}", self.base_url, id);
+        debug!("PATCH {} with request: {:?}", url, request);
+
+        let response = self.http()?.patch(&url).json(&request).send().await?;
+
+        let body = read_body(response).await?;
+        let result: RawMemory = parse_body(&body)?;
+        Ok(result.into())
+    }
+
+    pub async fn forget(&self, id: &str, as_actor: &str) -> Result<(), BerryError> {
+        let url = format!("{}/v1/memory/{}", self.base_url, id);
+        debug!("DELETE {}", url);
+
+        let response = self
+            .http()?
+            .delete(&url)
+            .query(&[("asActor", as_actor)])
+            .send()
+            .await?;
+
+        read_body(response).await?;
+        Ok(())
+    }
+}
+
+/// Read the response body, turning non-success statuses into `BerryError::Server`,
+/// or `BerryError::Auth` when the credentials were missing or rejected
+async fn read_body(response: reqwest::Response) -> Result<String, BerryError> {
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let message = response.text().await.unwrap_or_default();
+        error!("Server error: {} - {}", status, message);
+        // 403 means the credentials were accepted but don't allow this request
+        if status == 401 {
+            return Err(BerryError::Auth { status, message });
+        }
+        return Err(BerryError::Server { status, message });
+    }
+
+    let body = response.text().await?;
+    debug!("Response body: {}", &body[..body.len().min(500)]);
+    Ok(body)
+}
+
+fn parse_body<T: DeserializeOwned>(body: &str) -> Result<T, BerryError> {
+    serde_json::from_str(body).map_err(|e| {
+        error!("Failed to parse response: {}", e);
+        error!("Response body was: {}", &body[..body.len().min(1000)]);
+        BerryError::Parse(e.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::{block_on, fixture_memory, MockBerryServer};
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_health_reports_latency() {
+        let server = MockBerryServer::start(vec![]);
+        let client = BerryClient::new(server.url());
+
+        let latency = block_on(client.health()).expect("Health check failed");
+        assert!(latency < HEALTH_TIMEOUT);
+    }
+
+    #[test]
+    fn test_search_parses_fixture_memories() {
+        let mut untyped = fixture_memory("mem-2", "Review the backlog");
+        untyped.as_object_mut().unwrap().remove("type");
+        let server = MockBerryServer::start(vec![
+            fixture_memory("mem-1", "Backlog grooming notes"),
+            untyped,
+            fixture_memory("mem-3", "Unrelated"),
+        ]);
+        let client = BerryClient::new(server.url());
+
+        let page = block_on(client.search(SearchRequest {
+            query: "backlog".to_string(),
+            as_actor: "persona-ui".to_string(),
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        }))
+        .expect("Search failed");
+
+        assert_eq!(page.total, 2);
+        assert_eq!(page.memories.len(), 1);
+        assert_eq!(page.memories[0].id, "mem-2");
+        // A missing type falls back to the default
+        assert_eq!(page.memories[0].memory_type, MemoryType::Information);
+
+        let request: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(request["asActor"], "persona-ui");
+        assert_eq!(request["limit"], 1);
+    }
+
+    #[test]
+    fn test_search_sends_and_applies_filters() {
+        let mut tagged = fixture_memory("mem-1", "Backlog for review");
+        tagged["tags"] = json!(["review", "berry"]);
+        let mut decision = fixture_memory("mem-2", "Backlog decision");
+        decision["tags"] = json!(["review"]);
+        decision["type"] = json!("decision");
+        let mut other_author = fixture_memory("mem-3", "Backlog from someone else");
+        other_author["tags"] = json!(["review", "berry"]);
+        other_author["created_by"] = json!("tech-review-professional");
+        let server = MockBerryServer::start(vec![tagged, decision, other_author]);
+        let client = BerryClient::new(server.url());
+
+        let page = block_on(client.search(SearchRequest {
+            query: "backlog".to_string(),
+            as_actor: "persona-ui".to_string(),
+            tags: Some(vec!["review".to_string(), "berry".to_string()]),
+            memory_type: Some(MemoryType::Information),
+            created_by: Some("staff-mentor".to_string()),
+            ..Default::default()
+        }))
+        .expect("Search failed");
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.memories[0].id, "mem-1");
+
+        let request: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(request["tags"], json!(["review", "berry"]));
+        assert_eq!(request["type"], "information");
+        assert_eq!(request["createdBy"], "staff-mentor");
+
+        let decisions = block_on(client.search(SearchRequest {
+            query: "backlog".to_string(),
+            memory_type: Some(MemoryType::Decision),
+            ..Default::default()
+        }))
+        .expect("Search failed");
+        assert_eq!(decisions.total, 1);
+        assert_eq!(decisions.memories[0].id, "mem-2");
+    }
+
+    #[test]
+    fn test_remember_update_and_forget() {
+        let server = MockBerryServer::start(vec![]);
+        let client = BerryClient::new(server.url());
+
+        let created = block_on(client.remember(RememberRequest {
+            content: "Draft".to_string(),
+            created_by: "persona-ui".to_string(),
+            memory_type: MemoryType::Request,
+            tags: vec!["assignment".to_string()],
+            visibility: Visibility::Shared,
+            shared_with: vec!["staff-mentor".to_string()],
+        }))
+        .expect("Remember failed");
+        assert_eq!(created.memory_type, MemoryType::Request);
+        assert_eq!(created.visibility, Visibility::Shared);
+
+        let updated = block_on(client.update_memory(
+            &created.id,
+            UpdateMemoryRequest {
+                as_actor: "persona-ui".to_string(),
+                content: Some("Final".to_string()),
+                ..Default::default()
+            },
+        ))
+        .expect("Update failed");
+        assert_eq!(updated.content, "Final");
+        assert_eq!(updated.tags, vec!["assignment"]);
+
+        block_on(client.forget(&created.id, "persona-ui")).expect("Forget failed");
+        assert!(server.memories().is_empty());
+        assert_eq!(server.requests()[2].query, "asActor=persona-ui");
+    }
+
+    #[test]
+    fn test_server_error_is_reported() {
+        let server = MockBerryServer::start(vec![fixture_memory("mem-1", "Hello")]);
+        server.fail_next(500, "Chroma unavailable");
+        let client = BerryClient::new(server.url());
+
+        let error = block_on(client.get_memory("mem-1", "persona-ui")).unwrap_err();
+        match &error {
+            BerryError::Server { status, message } => {
+                assert_eq!(*status, 500);
+                assert_eq!(message, "Chroma unavailable");
+            }
+            other => panic!("Expected server error, got {:?}", other),
         }
+        assert!(!error.is_unreachable());
+
+        let missing = block_on(client.get_memory("mem-9", "persona-ui")).unwrap_err();
+        assert!(matches!(missing, BerryError::Server { status: 404, .. }));
+    }
+
+    #[test]
+    fn test_auth_headers_are_sent() {
+        let server = MockBerryServer::start(vec![fixture_memory("mem-1", "Hello")]);
+        server.require_header("x-berry-token", "s3cret");
 
-        let body = response.text().await?;
-        debug!("Response body: {}", &body[..body.len().min(500)]);
+        let anonymous = BerryClient::new(server.url());
+        let error = block_on(anonymous.get_memory("mem-1", "persona-ui")).unwrap_err();
+        assert!(matches!(error, BerryError::Auth { status: 401, .. }));
+        assert!(!error.is_unreachable());
+
+        let client = BerryClient::new(server.url()).with_auth(&BerryAuth::Header {
+            name: "X-Berry-Token".to_string(),
+            token: "s3cret".to_string(),
+        });
+        block_on(client.get_memory("mem-1", "persona-ui")).expect("Authenticated request failed");
+
+        let bearer = BerryClient::new(server.url()).with_auth(&BerryAuth::Bearer("s3cret".into()));
+        block_on(bearer.health()).unwrap_err();
+        assert_eq!(
+            server.requests().last().unwrap().header("authorization"),
+            Some("Bearer s3cret")
+        );
+    }
+
+    #[test]
+    fn test_forbidden_is_not_an_auth_failure() {
+        let server = MockBerryServer::start(vec![fixture_memory("mem-1", "Hello")]);
+        server.fail_next(403, "Not shared with persona-ui");
+        let client = BerryClient::new(server.url());
+
+        let error = block_on(client.get_memory("mem-1", "persona-ui")).unwrap_err();
+        assert!(matches!(error, BerryError::Server { status: 403, .. }));
+        assert!(!error.is_auth_failure());
+    }
+
+    #[test]
+    fn test_invalid_auth_fails_without_sending() {
+        let server = MockBerryServer::start(vec![]);
+        let auth = BerryAuth::Header {
+            name: "Bad Header".to_string(),
+            token: "s3cret".to_string(),
+        };
+        assert!(matches!(auth.validate(), Err(BerryError::InvalidAuth(_))));
+
+        let client = BerryClient::new(server.url()).with_auth(&auth);
+        let error = block_on(client.health()).unwrap_err();
+        assert!(matches!(error, BerryError::InvalidAuth(_)));
+        assert!(error.is_auth_failure());
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_response_is_a_parse_error() {
+        let server = MockBerryServer::start(vec![]);
+        server.fail_next(200, &json!({ "success": true }).to_string());
+        let client = BerryClient::new(server.url());
+
+        let error = block_on(client.search(SearchRequest::default())).unwrap_err();
+        assert!(matches!(error, BerryError::Parse(_)));
+    }
 
-        let result: SearchResponse = serde_json::from_str(&body).map_err(|e| {
-            error!("Failed to parse response: {}", e);
-            error!("Response body was: {}", &body[..body.len().min(1000)]);
-            BerryError::Parse(e.to_string())
-        })?;
+    #[test]
+    fn test_stopped_server_is_unreachable() {
+        let server = MockBerryServer::start(vec![]);
+        let client = BerryClient::new(server.url());
+        drop(server);
 
-        Ok(result.memories.into_iter().map(Memory::from).collect())
+        let error = block_on(client.health()).unwrap_err();
+        assert!(error.is_unreachable());
     }
 }
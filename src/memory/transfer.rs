@@ -0,0 +1,427 @@
+use super::{
+    BerryClient, BerryError, Memory, MemoryType, RawMemory, RememberRequest, SearchRequest,
+};
+use crate::config::data_dir;
+use crate::knowledgebase::{with_frontmatter, Frontmatter};
+use chrono::Utc;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+/// Page size used when fetching every memory for an export
+const EXPORT_PAGE_SIZE: u32 = 100;
+
+/// Tag added to memories that were promoted into a knowledgebase document
+pub const PROMOTED_TAG: &str = "promoted";
+
+/// Longest title taken from a memory's first line
+const TITLE_LENGTH: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Jsonl,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Jsonl => write!(f, "JSONL"),
+            ExportFormat::Markdown => write!(f, "Markdown"),
+        }
+    }
+}
+
+/// Directory exports are written to
+pub fn exports_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("exports"))
+}
+
+/// Fetch every memory matching a search, following pages until the total is reached
+pub async fn fetch_all(
+    client: &BerryClient,
+    request: SearchRequest,
+) -> Result<Vec<Memory>, BerryError> {
+    let mut memories: Vec<Memory> = Vec::new();
+
+    loop {
+        let page = client
+            .search(SearchRequest {
+                limit: Some(EXPORT_PAGE_SIZE),
+                offset: Some(memories.len() as u32),
+                ..request.clone()
+            })
+            .await?;
+
+        let received = page.memories.len();
+        memories.extend(page.memories);
+        if received == 0 || memories.len() >= page.total {
+            break;
+        }
+    }
+
+    Ok(memories)
+}
+
+pub fn export_memories(memories: &[Memory], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Jsonl => to_jsonl(memories),
+        ExportFormat::Markdown => to_markdown(memories),
+    }
+}
+
+/// One `RawMemory` JSON object per line
+pub fn to_jsonl(memories: &[Memory]) -> String {
+    memories
+        .iter()
+        .filter_map(|memory| serde_json::to_string(&RawMemory::from(memory)).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Human readable export grouped by memory type
+pub fn to_markdown(memories: &[Memory]) -> String {
+    let mut output = format!(
+        "# Memories\n\nExported {} memories on {}\n",
+        memories.len(),
+        Utc::now().format("%Y-%m-%d %H:%M UTC")
+    );
+
+    // Known types first, then any other types in the order they appear
+    let mut types: Vec<&MemoryType> = MemoryType::all().iter().collect();
+    for memory in memories {
+        if !types.contains(&&memory.memory_type) {
+            types.push(&memory.memory_type);
+        }
+    }
+
+    for memory_type in types {
+        let group: Vec<&Memory> = memories
+            .iter()
+            .filter(|m| &m.memory_type == memory_type)
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+
+        output.push_str(&format!("\n## {}\n", memory_type));
+        for memory in group {
+            output.push_str(&format!("\n### {}\n\n", memory.id));
+            output.push_str(&format!("- **Author:** {}\n", memory.created_by));
+            output.push_str(&format!(
+                "- **Created:** {}\n",
+                memory.created_at.format("%Y-%m-%d %H:%M UTC")
+            ));
+            if !memory.tags.is_empty() {
+                output.push_str(&format!("- **Tags:** {}\n", memory.tags.join(", ")));
+            }
+            output.push_str(&format!("- **Visibility:** {}\n", memory.visibility));
+            if !memory.shared_with.is_empty() {
+                output.push_str(&format!(
+                    "- **Shared With:** {}\n",
+                    memory.shared_with.join(", ")
+                ));
+            }
+            output.push_str(&format!("\n{}\n", memory.content.trim()));
+        }
+    }
+
+    output
+}
+
+/// Title for a knowledgebase document made from a memory, taken from its first line
+pub fn document_title(memory: &Memory) -> String {
+    let first_line = memory
+        .content
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+
+    if first_line.is_empty() {
+        return format!("{} {}", memory.memory_type, memory.id);
+    }
+    if first_line.chars().count() > TITLE_LENGTH {
+        let truncated: String = first_line.chars().take(TITLE_LENGTH).collect();
+        return format!("{}...", truncated.trim_end());
+    }
+    first_line.to_string()
+}
+
+/// Content of a memory without its leading heading when that heading became the
+/// document title, so the title isn't repeated
+fn document_body<'a>(memory: &'a Memory, title: &str) -> &'a str {
+    let content = memory.content.trim();
+    let (first_line, rest) = content.split_once('\n').unwrap_or((content, ""));
+    let first_line = first_line.trim();
+    if first_line.starts_with('#') && first_line.trim_start_matches('#').trim() == title {
+        rest.trim()
+    } else {
+        content
+    }
+}
+
+/// Knowledgebase document made from a single memory, with its metadata as
+/// frontmatter so it is tagged like any other document
+pub fn to_document(memory: &Memory) -> String {
+    let title = document_title(memory);
+    let document = format!("# {}\n\n{}\n", title, document_body(memory, &title));
+
+    let text = |value: String| serde_yaml::Value::String(value);
+    let frontmatter = Frontmatter {
+        tags: memory.tags.clone(),
+        extra: BTreeMap::from([
+            ("type".to_string(), text(memory.memory_type.to_string())),
+            (
+                "date".to_string(),
+                text(memory.created_at.format("%Y-%m-%d").to_string()),
+            ),
+            ("author".to_string(), text(memory.created_by.clone())),
+            ("berry_id".to_string(), text(memory.id.clone())),
+        ]),
+        ..Default::default()
+    };
+    // Only fails on frontmatter that isn't valid YAML, which a new document has none of
+    with_frontmatter(&document, &frontmatter).unwrap_or(document)
+}
+
+/// Parse a JSONL export, reporting the first line that fails
+pub fn parse_jsonl(input: &str) -> Result<Vec<Memory>, BerryError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<RawMemory>(line)
+                .map(Memory::from)
+                .map_err(|e| BerryError::Parse(format!("line {}: {}", index + 1, e)))
+        })
+        .collect()
+}
+
+/// Records from an import file split into those to create and those already present
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub new: Vec<Memory>,
+    pub duplicates: Vec<Memory>,
+}
+
+/// A record is a duplicate if its ID or its author and content are already known,
+/// either on the server or earlier in the same file
+pub fn plan_import(records: Vec<Memory>, existing: &[Memory]) -> ImportPlan {
+    let mut ids: HashSet<String> = existing.iter().map(|m| m.id.clone()).collect();
+    let mut contents: HashSet<(String, String)> = existing.iter().map(content_key).collect();
+
+    let mut plan = ImportPlan::default();
+    for record in records {
+        let key = content_key(&record);
+        if ids.contains(&record.id) || contents.contains(&key) {
+            plan.duplicates.push(record);
+        } else {
+            ids.insert(record.id.clone());
+            contents.insert(key);
+            plan.new.push(record);
+        }
+    }
+
+    plan
+}
+
+fn content_key(memory: &Memory) -> (String, String) {
+    (memory.created_by.clone(), memory.content.trim().to_string())
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    /// IDs of records the server rejected, with the error message
+    pub failed: Vec<(String, String)>,
+}
+
+/// Create each record through `BerryClient`, keeping the original author and sharing
+pub async fn import(client: &BerryClient, records: Vec<Memory>) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for record in records {
+        let request = RememberRequest {
+            content: record.content,
+            created_by: record.created_by,
+            memory_type: record.memory_type,
+            tags: record.tags,
+            visibility: record.visibility,
+            shared_with: record.shared_with,
+        };
+        match client.remember(request).await {
+            Ok(_) => report.imported += 1,
+            Err(e) => report.failed.push((record.id, e.to_string())),
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledgebase::body;
+    use crate::memory::mock::{block_on, fixture_memory, MemoryBuilder, MockBerryServer};
+    use crate::memory::Visibility;
+
+    #[test]
+    fn test_jsonl_roundtrip() {
+        let memories = vec![
+            MemoryBuilder::new("mem-1", "First")
+                .memory_type(MemoryType::Question)
+                .shared_with(&["persona-ui"])
+                .build(),
+            MemoryBuilder::new("mem-2", "Second\nline")
+                .shared_with(&["persona-ui"])
+                .build(),
+        ];
+
+        let jsonl = to_jsonl(&memories);
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl.contains("\"created_by\":\"staff-mentor\""));
+        assert!(jsonl.contains("\"visibility\":\"shared\""));
+
+        let parsed = parse_jsonl(&jsonl).expect("Failed to parse");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].content, "Second\nline");
+        assert_eq!(parsed[1].visibility, Visibility::Shared);
+        assert_eq!(parsed[0].shared_with, vec!["persona-ui"]);
+    }
+
+    #[test]
+    fn test_parse_jsonl_reports_line() {
+        let input = format!("{}\n\nnot json\n", fixture_memory("mem-1", "First"));
+        let error = parse_jsonl(&input).unwrap_err();
+        assert!(error.to_string().contains("line 3"));
+    }
+
+    #[test]
+    fn test_markdown_groups_by_type() {
+        let markdown = to_markdown(&[
+            MemoryBuilder::new("mem-3", "Weekly retro")
+                .memory_type(MemoryType::Other("retro".to_string()))
+                .build(),
+            MemoryBuilder::new("mem-1", "What next?")
+                .memory_type(MemoryType::Question)
+                .build(),
+            MemoryBuilder::new("mem-2", "Background")
+                .shared_with(&["persona-ui"])
+                .build(),
+        ]);
+
+        let question = markdown
+            .find("## Question")
+            .expect("Missing question group");
+        let information = markdown
+            .find("## Information")
+            .expect("Missing information group");
+        let retro = markdown.find("## retro").expect("Missing other group");
+        assert!(question < information);
+        assert!(information < retro);
+        assert!(!markdown.contains("## Request"));
+        assert!(markdown.contains("- **Shared With:** persona-ui"));
+    }
+
+    #[test]
+    fn test_document_has_metadata_frontmatter() {
+        let decision = MemoryBuilder::new("mem-1", "## Release plan\n\nShip on Friday")
+            .memory_type(MemoryType::Decision)
+            .tags(&["release", "mentor"])
+            .build();
+
+        assert_eq!(document_title(&decision), "Release plan");
+
+        let document = to_document(&decision);
+        let frontmatter = Frontmatter::from_document(&document);
+        assert_eq!(frontmatter.tags, vec!["release", "mentor"]);
+        let field = |key: &str| frontmatter.extra.get(key).and_then(|v| v.as_str());
+        assert_eq!(field("type"), Some("Decision"));
+        assert_eq!(field("date"), Some("2026-01-13"));
+        assert_eq!(field("berry_id"), Some("mem-1"));
+        assert_eq!(body(&document), "# Release plan\n\nShip on Friday\n");
+        assert_eq!(document.matches("Release plan").count(), 1);
+
+        // Plain first lines and shortened headings stay in the body
+        let plain = MemoryBuilder::new("mem-2", "Ship on Friday").build();
+        assert!(to_document(&plain).ends_with("# Ship on Friday\n\nShip on Friday\n"));
+        let heading = format!("# {}", "long ".repeat(30));
+        let long = MemoryBuilder::new("mem-3", &format!("{}\n\nBody", heading)).build();
+        assert!(to_document(&long).contains(heading.trim()));
+    }
+
+    #[test]
+    fn test_document_title_falls_back_to_type() {
+        let blank = MemoryBuilder::new("mem-1", "   \n")
+            .memory_type(MemoryType::Question)
+            .build();
+        assert_eq!(document_title(&blank), "Question mem-1");
+
+        let long = MemoryBuilder::new("mem-2", &"word ".repeat(40))
+            .memory_type(MemoryType::Question)
+            .build();
+        assert!(document_title(&long).ends_with("..."));
+    }
+
+    #[test]
+    fn test_plan_import_detects_duplicates() {
+        let existing = vec![MemoryBuilder::new("mem-1", "Known").build()];
+        let records = vec![
+            MemoryBuilder::new("mem-1", "Same id").build(),
+            MemoryBuilder::new("other-id", " Known ").build(),
+            MemoryBuilder::new("mem-2", "New").build(),
+            MemoryBuilder::new("mem-3", "New").build(),
+        ];
+
+        let plan = plan_import(records, &existing);
+        assert_eq!(plan.new.len(), 1);
+        assert_eq!(plan.new[0].id, "mem-2");
+        assert_eq!(plan.duplicates.len(), 3);
+    }
+
+    #[test]
+    fn test_fetch_all_and_import() {
+        let source: Vec<_> = (0..150)
+            .map(|i| fixture_memory(&format!("mem-{}", i), &format!("Memory {}", i)))
+            .collect();
+        let source_server = MockBerryServer::start(source);
+        let source_client = BerryClient::new(source_server.url());
+
+        let exported = block_on(fetch_all(
+            &source_client,
+            SearchRequest {
+                as_actor: "persona-ui".to_string(),
+                ..Default::default()
+            },
+        ))
+        .expect("Failed to fetch");
+        assert_eq!(exported.len(), 150);
+
+        let target_server = MockBerryServer::start(vec![fixture_memory("mem-0", "Memory 0")]);
+        let target_client = BerryClient::new(target_server.url());
+
+        let records = parse_jsonl(&to_jsonl(&exported)).expect("Failed to parse");
+        let plan = plan_import(
+            records,
+            &[Memory::from(
+                serde_json::from_value::<RawMemory>(fixture_memory("mem-0", "Memory 0")).unwrap(),
+            )],
+        );
+        assert_eq!(plan.duplicates.len(), 1);
+
+        let report = block_on(import(&target_client, plan.new));
+        assert_eq!(report.imported, 149);
+        assert!(report.failed.is_empty());
+        assert_eq!(target_server.memories().len(), 150);
+    }
+}
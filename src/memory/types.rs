@@ -1,12 +1,64 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+/// Kind of memory, any type Berry returns that isn't known here is kept as `Other`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MemoryType {
     Question,
     Request,
     Information,
+    Assignment,
+    Decision,
+    SessionSummary,
+    Other(String),
+}
+
+impl MemoryType {
+    /// Known types, in the order they are offered in the UI
+    pub fn all() -> &'static [MemoryType] {
+        &[
+            MemoryType::Question,
+            MemoryType::Request,
+            MemoryType::Information,
+            MemoryType::Assignment,
+            MemoryType::Decision,
+            MemoryType::SessionSummary,
+        ]
+    }
+
+    /// Value used for the `type` field in Berry requests
+    pub fn as_str(&self) -> &str {
+        match self {
+            MemoryType::Question => "question",
+            MemoryType::Request => "request",
+            MemoryType::Information => "information",
+            MemoryType::Assignment => "assignment",
+            MemoryType::Decision => "decision",
+            MemoryType::SessionSummary => "session_summary",
+            MemoryType::Other(raw) => raw,
+        }
+    }
+
+    /// Parse a type ignoring case and separators, so `session_summary`,
+    /// `sessionSummary` and `Session Summary` are all the same type
+    pub fn parse(raw: &str) -> Self {
+        let normalized: String = raw
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect();
+
+        match normalized.as_str() {
+            "question" => MemoryType::Question,
+            "request" => MemoryType::Request,
+            "information" => MemoryType::Information,
+            "assignment" => MemoryType::Assignment,
+            "decision" => MemoryType::Decision,
+            "sessionsummary" => MemoryType::SessionSummary,
+            _ => MemoryType::Other(raw.trim().to_string()),
+        }
+    }
 }
 
 impl std::fmt::Display for MemoryType {
@@ -15,16 +67,97 @@ impl std::fmt::Display for MemoryType {
             MemoryType::Question => write!(f, "Question"),
             MemoryType::Request => write!(f, "Request"),
             MemoryType::Information => write!(f, "Information"),
+            MemoryType::Assignment => write!(f, "Assignment"),
+            MemoryType::Decision => write!(f, "Decision"),
+            MemoryType::SessionSummary => write!(f, "Session Summary"),
+            MemoryType::Other(raw) => write!(f, "{}", raw),
+        }
+    }
+}
+
+impl Serialize for MemoryType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for MemoryType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(MemoryType::parse(&raw))
+    }
+}
+
+/// Who can see a memory, a visibility Berry returns that isn't known here is kept
+/// as `Other` so it is never widened when the memory is shown or written back
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Visibility {
+    Private,
+    Shared,
+    #[default]
+    Public,
+    Other(String),
+}
+
+impl Visibility {
+    /// Known visibilities, in the order they are offered in the UI
+    pub fn all() -> &'static [Visibility] {
+        &[Visibility::Private, Visibility::Shared, Visibility::Public]
+    }
+
+    /// The value sent to and stored by Berry
+    pub fn as_str(&self) -> &str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Shared => "shared",
+            Visibility::Public => "public",
+            Visibility::Other(raw) => raw,
+        }
+    }
+
+    /// Parse the visibility string returned by Berry, a missing value being the
+    /// server's default
+    pub fn from_raw(raw: &str) -> Self {
+        match raw.trim().to_lowercase().as_str() {
+            "private" => Visibility::Private,
+            "shared" => Visibility::Shared,
+            "public" => Visibility::Public,
+            "" => Visibility::default(),
+            _ => Visibility::Other(raw.trim().to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Visibility::Private => write!(f, "Private"),
+            Visibility::Shared => write!(f, "Shared"),
+            Visibility::Public => write!(f, "Public"),
+            Visibility::Other(raw) => write!(f, "{}", raw),
         }
     }
 }
 
+impl Serialize for Visibility {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Visibility {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Visibility::from_raw(&raw))
+    }
+}
+
 fn default_memory_type() -> MemoryType {
     MemoryType::Information
 }
 
 /// Raw memory as returned by berry-rs API (flat structure, snake_case)
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawMemory {
     pub id: String,
     pub content: String,
@@ -42,14 +175,41 @@ pub struct RawMemory {
 }
 
 /// Flattened memory for easier use in the UI
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Memory {
     pub id: String,
     pub content: String,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
     pub created_by: String,
     pub tags: Vec<String>,
     pub memory_type: MemoryType,
+    pub visibility: Visibility,
+    pub shared_with: Vec<String>,
+}
+
+impl Memory {
+    /// Short description of who can see the memory
+    pub fn sharing_summary(&self) -> String {
+        match &self.visibility {
+            Visibility::Shared if !self.shared_with.is_empty() => {
+                format!("Shared ({})", self.shared_with.len())
+            }
+            visibility => visibility.to_string(),
+        }
+    }
+
+    /// Whether the given actor is allowed to see this memory
+    pub fn is_visible_to(&self, actor: &str) -> bool {
+        match &self.visibility {
+            Visibility::Public => true,
+            // Unknown visibilities are treated as private rather than guessed at
+            Visibility::Private | Visibility::Other(_) => self.created_by == actor,
+            Visibility::Shared => {
+                self.created_by == actor || self.shared_with.iter().any(|a| a == actor)
+            }
+        }
+    }
 }
 
 impl From<RawMemory> for Memory {
@@ -59,8 +219,11 @@ impl From<RawMemory> for Memory {
             content: raw.content,
             memory_type: raw.memory_type,
             created_at: raw.created_at,
+            updated_at: raw.updated_at,
             created_by: raw.created_by,
             tags: raw.tags,
+            visibility: Visibility::from_raw(&raw.visibility),
+            shared_with: raw.shared_with,
         }
     }
 }
@@ -73,9 +236,98 @@ pub struct SearchRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub memory_type: Option<MemoryType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+/// A page of search results along with the total number of matches
+#[derive(Debug, Clone, Default)]
+pub struct SearchPage {
+    pub memories: Vec<Memory>,
+    pub total: usize,
+}
+
+impl From<&Memory> for RawMemory {
+    fn from(memory: &Memory) -> Self {
+        Self {
+            id: memory.id.clone(),
+            content: memory.content.clone(),
+            memory_type: memory.memory_type.clone(),
+            tags: memory.tags.clone(),
+            created_by: memory.created_by.clone(),
+            created_at: memory.created_at,
+            updated_at: memory.updated_at,
+            visibility: memory.visibility.as_str().to_string(),
+            shared_with: memory.shared_with.clone(),
+        }
+    }
+}
+
+/// Request body for creating a memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RememberRequest {
+    pub content: String,
+    pub created_by: String,
+    #[serde(rename = "type")]
+    pub memory_type: MemoryType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub visibility: Visibility,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_with: Vec<String>,
+}
+
+/// Request body for updating a memory, only the fields that are set are changed
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMemoryRequest {
+    pub as_actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<MemoryType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_with: Option<Vec<String>>,
+}
+
+/// Connection state of the Berry server as seen by the last health check
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BerryStatus {
+    #[default]
+    Unknown,
+    Online {
+        latency: Duration,
+    },
+    Offline(String),
+    /// Reachable, but the configured credentials were rejected
+    Unauthorized(String),
+}
+
+impl BerryStatus {
+    pub fn is_offline(&self) -> bool {
+        matches!(self, BerryStatus::Offline(_))
+    }
+}
+
+impl std::fmt::Display for BerryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BerryStatus::Unknown => write!(f, "Checking..."),
+            BerryStatus::Online { latency } => write!(f, "Online ({} ms)", latency.as_millis()),
+            BerryStatus::Offline(_) => write!(f, "Offline"),
+            BerryStatus::Unauthorized(_) => write!(f, "Unauthorized"),
+        }
+    }
 }
 
 /// Response from berry-rs search endpoint
@@ -88,3 +340,194 @@ pub struct SearchResponse {
     #[serde(default)]
     pub error: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remember_request_serialization() {
+        let request = RememberRequest {
+            content: "Review the backlog".to_string(),
+            created_by: "persona-ui".to_string(),
+            memory_type: MemoryType::Request,
+            tags: vec!["assignment".to_string()],
+            visibility: Visibility::Shared,
+            shared_with: vec!["staff-mentor".to_string()],
+        };
+
+        let json = serde_json::to_value(&request).expect("Failed to serialize");
+        assert_eq!(json["content"], "Review the backlog");
+        assert_eq!(json["createdBy"], "persona-ui");
+        assert_eq!(json["type"], "request");
+        assert_eq!(json["tags"][0], "assignment");
+        assert_eq!(json["visibility"], "shared");
+        assert_eq!(json["sharedWith"][0], "staff-mentor");
+    }
+
+    #[test]
+    fn test_remember_request_skips_empty_lists() {
+        let request = RememberRequest {
+            content: "How do I measure impact?".to_string(),
+            created_by: "persona-ui".to_string(),
+            memory_type: MemoryType::Question,
+            tags: vec![],
+            visibility: Visibility::default(),
+            shared_with: vec![],
+        };
+
+        let json = serde_json::to_value(&request).expect("Failed to serialize");
+        assert!(json.get("tags").is_none());
+        assert!(json.get("sharedWith").is_none());
+        assert_eq!(json["visibility"], "public");
+    }
+
+    #[test]
+    fn test_update_request_only_serializes_set_fields() {
+        let request = UpdateMemoryRequest {
+            as_actor: "persona-ui".to_string(),
+            content: Some("Updated".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&request).expect("Failed to serialize");
+        assert_eq!(json["asActor"], "persona-ui");
+        assert_eq!(json["content"], "Updated");
+        assert!(json.get("type").is_none());
+        assert!(json.get("tags").is_none());
+        assert!(json.get("visibility").is_none());
+        assert!(json.get("sharedWith").is_none());
+    }
+
+    #[test]
+    fn test_memory_keeps_visibility_and_shared_with() {
+        let json = r#"{
+            "id": "mem-1",
+            "content": "Shared context",
+            "type": "information",
+            "created_by": "staff-mentor",
+            "created_at": "2026-01-13T09:33:09Z",
+            "updated_at": "2026-01-13T09:33:09Z",
+            "visibility": "shared",
+            "shared_with": ["tech-review-professional"]
+        }"#;
+
+        let raw: RawMemory = serde_json::from_str(json).expect("Failed to deserialize");
+        let memory = Memory::from(raw);
+
+        assert_eq!(memory.visibility, Visibility::Shared);
+        assert_eq!(memory.shared_with, vec!["tech-review-professional"]);
+        assert_eq!(memory.sharing_summary(), "Shared (1)");
+    }
+
+    #[test]
+    fn test_berry_status_display() {
+        let online = BerryStatus::Online {
+            latency: Duration::from_millis(12),
+        };
+        assert_eq!(online.to_string(), "Online (12 ms)");
+        assert!(!online.is_offline());
+
+        let offline = BerryStatus::Offline("connection refused".to_string());
+        assert_eq!(offline.to_string(), "Offline");
+        assert!(offline.is_offline());
+    }
+
+    #[test]
+    fn test_visibility_from_raw_keeps_unknown_values() {
+        assert_eq!(Visibility::from_raw("Private"), Visibility::Private);
+        assert_eq!(Visibility::from_raw(""), Visibility::default());
+        assert_eq!(
+            Visibility::from_raw("team"),
+            Visibility::Other("team".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_visibility_round_trips_without_widening() {
+        let json = r#"{
+            "id": "mem-1",
+            "content": "Team context",
+            "type": "information",
+            "created_by": "staff-mentor",
+            "created_at": "2026-01-13T09:33:09Z",
+            "updated_at": "2026-01-13T09:33:09Z",
+            "visibility": "team"
+        }"#;
+
+        let raw: RawMemory = serde_json::from_str(json).expect("Failed to deserialize");
+        let memory = Memory::from(raw);
+        assert_eq!(memory.sharing_summary(), "team");
+        assert!(memory.is_visible_to("staff-mentor"));
+        assert!(!memory.is_visible_to("tech-review-professional"));
+
+        assert_eq!(RawMemory::from(&memory).visibility, "team");
+        let json = serde_json::to_value(&memory).unwrap();
+        assert_eq!(json["visibility"], "team");
+        let parsed: Memory = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.visibility, memory.visibility);
+    }
+
+    #[test]
+    fn test_memory_type_parses_known_and_unknown_types() {
+        assert_eq!(MemoryType::parse("assignment"), MemoryType::Assignment);
+        assert_eq!(
+            MemoryType::parse("session_summary"),
+            MemoryType::SessionSummary
+        );
+        assert_eq!(
+            MemoryType::parse("sessionSummary"),
+            MemoryType::SessionSummary
+        );
+        assert_eq!(
+            MemoryType::parse("Session Summary"),
+            MemoryType::SessionSummary
+        );
+        assert_eq!(
+            MemoryType::parse("retro"),
+            MemoryType::Other("retro".to_string())
+        );
+
+        for memory_type in MemoryType::all() {
+            assert_eq!(&MemoryType::parse(&memory_type.to_string()), memory_type);
+            assert_eq!(&MemoryType::parse(memory_type.as_str()), memory_type);
+        }
+    }
+
+    #[test]
+    fn test_unknown_type_does_not_fail_search_response() {
+        let json = r#"{
+            "success": true,
+            "total": 2,
+            "memories": [
+                {
+                    "id": "mem-1",
+                    "content": "Weekly retro",
+                    "type": "retro",
+                    "created_by": "staff-mentor",
+                    "created_at": "2026-01-13T09:33:09Z",
+                    "updated_at": "2026-01-13T09:33:09Z"
+                },
+                {
+                    "id": "mem-2",
+                    "content": "Ship it",
+                    "type": "decision",
+                    "created_by": "staff-mentor",
+                    "created_at": "2026-01-13T09:33:09Z",
+                    "updated_at": "2026-01-13T09:33:09Z"
+                }
+            ]
+        }"#;
+
+        let response: SearchResponse = serde_json::from_str(json).expect("Failed to deserialize");
+        assert_eq!(
+            response.memories[0].memory_type,
+            MemoryType::Other("retro".to_string())
+        );
+        assert_eq!(response.memories[1].memory_type, MemoryType::Decision);
+
+        let raw = RawMemory::from(&Memory::from(response.memories[0].clone()));
+        let json = serde_json::to_value(&raw).expect("Failed to serialize");
+        assert_eq!(json["type"], "retro");
+    }
+}
@@ -0,0 +1,29 @@
+use crate::memory::MemoryType;
+use gpui::*;
+use gpui_component::ActiveTheme;
+
+/// Pill showing a memory type, colored per type so they can be told apart at a glance
+pub fn type_badge(memory_type: &MemoryType, cx: &App) -> Div {
+    let theme = cx.theme();
+    let color = match memory_type {
+        MemoryType::Question => theme.info,
+        MemoryType::Request => theme.warning,
+        MemoryType::Information => theme.muted_foreground,
+        MemoryType::Assignment => theme.primary,
+        MemoryType::Decision => theme.success,
+        MemoryType::SessionSummary => theme.chart_4,
+        MemoryType::Other(_) => theme.chart_5,
+    };
+
+    div()
+        .px_1p5()
+        .rounded_md()
+        .text_xs()
+        .text_color(color)
+        .bg(color.opacity(0.15))
+        .border_1()
+        .border_color(color.opacity(0.4))
+        .overflow_hidden()
+        .whitespace_nowrap()
+        .child(memory_type.to_string())
+}
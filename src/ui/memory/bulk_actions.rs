@@ -0,0 +1,264 @@
+use crate::memory::{MemoryType, Visibility};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    select::{Select, SelectState},
+    v_flex, ActiveTheme, Disableable, Sizable,
+};
+
+use super::parse_list;
+
+#[derive(Debug, Clone)]
+pub enum BulkActionEvent {
+    AddTags(Vec<String>),
+    RemoveTags(Vec<String>),
+    SetType(MemoryType),
+    SetVisibility(Visibility),
+    Delete,
+    SelectAll,
+    ClearSelection,
+}
+
+impl EventEmitter<BulkActionEvent> for BulkActionBar {}
+
+/// Actions applied to every memory selected in the table
+pub struct BulkActionBar {
+    selected: usize,
+    tags_input: Entity<InputState>,
+    type_select: Entity<SelectState<Vec<String>>>,
+    visibility_select: Entity<SelectState<Vec<String>>>,
+    confirm_delete: bool,
+    busy: bool,
+}
+
+impl BulkActionBar {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let tags_input = cx.new(|cx| InputState::new(window, cx).placeholder("Tags..."));
+
+        let type_names: Vec<String> = MemoryType::all().iter().map(|t| t.to_string()).collect();
+        let type_select = cx.new(|cx| SelectState::new(type_names, None, window, cx));
+
+        let visibility_names: Vec<String> =
+            Self::visibilities().iter().map(|v| v.to_string()).collect();
+        let visibility_select = cx.new(|cx| SelectState::new(visibility_names, None, window, cx));
+
+        Self {
+            selected: 0,
+            tags_input,
+            type_select,
+            visibility_select,
+            confirm_delete: false,
+            busy: false,
+        }
+    }
+
+    /// Visibilities that can be set on many memories at once, leaving out Shared
+    /// as that needs actors chosen for each memory
+    fn visibilities() -> Vec<Visibility> {
+        Visibility::all()
+            .iter()
+            .filter(|v| **v != Visibility::Shared)
+            .cloned()
+            .collect()
+    }
+
+    pub fn set_selected(&mut self, selected: usize) {
+        if selected != self.selected {
+            self.confirm_delete = false;
+        }
+        self.selected = selected;
+    }
+
+    pub fn set_busy(&mut self, busy: bool) {
+        self.busy = busy;
+    }
+
+    fn tags(&self, cx: &App) -> Vec<String> {
+        parse_list(&self.tags_input.read(cx).text().to_string())
+    }
+
+    fn emit_tags(&mut self, add: bool, cx: &mut Context<Self>) {
+        let tags = self.tags(cx);
+        if tags.is_empty() {
+            return;
+        }
+        cx.emit(if add {
+            BulkActionEvent::AddTags(tags)
+        } else {
+            BulkActionEvent::RemoveTags(tags)
+        });
+    }
+
+    fn apply_type(&mut self, cx: &mut Context<Self>) {
+        let memory_type = self
+            .type_select
+            .read(cx)
+            .selected_value()
+            .and_then(|name| MemoryType::all().iter().find(|t| t.to_string() == *name))
+            .cloned();
+        if let Some(memory_type) = memory_type {
+            cx.emit(BulkActionEvent::SetType(memory_type));
+        }
+    }
+
+    fn apply_visibility(&mut self, cx: &mut Context<Self>) {
+        let visibility = self
+            .visibility_select
+            .read(cx)
+            .selected_value()
+            .and_then(|name| {
+                Self::visibilities()
+                    .into_iter()
+                    .find(|v| v.to_string() == *name)
+            });
+        if let Some(visibility) = visibility {
+            cx.emit(BulkActionEvent::SetVisibility(visibility));
+        }
+    }
+
+    /// First click asks for confirmation, second click deletes
+    fn delete(&mut self, cx: &mut Context<Self>) {
+        if self.confirm_delete {
+            self.confirm_delete = false;
+            cx.emit(BulkActionEvent::Delete);
+        } else {
+            self.confirm_delete = true;
+        }
+        cx.notify();
+    }
+}
+
+impl Render for BulkActionBar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let busy = self.busy;
+
+        v_flex()
+            .id("bulk-action-bar")
+            .w_full()
+            .px_4()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().muted)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(format!("{} selected", self.selected)),
+                    )
+                    .child(
+                        Button::new("bulk-select-all")
+                            .label("Select All")
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|_this, _, _window, cx| {
+                                cx.emit(BulkActionEvent::SelectAll);
+                            })),
+                    )
+                    .child(
+                        Button::new("bulk-clear-selection")
+                            .label("Clear Selection")
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|_this, _, _window, cx| {
+                                cx.emit(BulkActionEvent::ClearSelection);
+                            })),
+                    )
+                    .child(
+                        Button::new("bulk-delete")
+                            .label(if self.confirm_delete {
+                                format!("Confirm Delete {}", self.selected)
+                            } else {
+                                "Delete".to_string()
+                            })
+                            .danger()
+                            .xsmall()
+                            .disabled(busy)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.delete(cx);
+                            })),
+                    )
+                    .when(self.confirm_delete, |this| {
+                        this.child(
+                            Button::new("bulk-cancel-delete")
+                                .label("Cancel")
+                                .ghost()
+                                .xsmall()
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.confirm_delete = false;
+                                    cx.notify();
+                                })),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .items_center()
+                    .child(Input::new(&self.tags_input).small().w(px(200.)))
+                    .child(
+                        Button::new("bulk-add-tags")
+                            .label("Add Tags")
+                            .outline()
+                            .xsmall()
+                            .disabled(busy)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.emit_tags(true, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("bulk-remove-tags")
+                            .label("Remove Tags")
+                            .outline()
+                            .xsmall()
+                            .disabled(busy)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.emit_tags(false, cx);
+                            })),
+                    )
+                    .child(
+                        Select::new(&self.type_select)
+                            .small()
+                            .w(px(140.))
+                            .placeholder("Type"),
+                    )
+                    .child(
+                        Button::new("bulk-set-type")
+                            .label("Set Type")
+                            .outline()
+                            .xsmall()
+                            .disabled(busy)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.apply_type(cx);
+                            })),
+                    )
+                    .child(
+                        Select::new(&self.visibility_select)
+                            .small()
+                            .w(px(140.))
+                            .placeholder("Visibility"),
+                    )
+                    .child(
+                        Button::new("bulk-set-visibility")
+                            .label("Set Visibility")
+                            .outline()
+                            .xsmall()
+                            .disabled(busy)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.apply_visibility(cx);
+                            })),
+                    ),
+            )
+    }
+}
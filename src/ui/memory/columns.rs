@@ -0,0 +1,306 @@
+use crate::config::TableLayoutConfig;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{h_flex, ActiveTheme, Icon, IconName, Sizable};
+use std::collections::HashMap;
+
+/// Narrowest a column can be dragged to
+const MIN_COLUMN_WIDTH: f32 = 48.;
+
+/// A column of a data table
+pub struct Column {
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Default width in pixels, `None` for the column that fills the remaining space
+    pub width: Option<f32>,
+    pub sortable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+struct ColumnDrag {
+    column: &'static str,
+    start_x: Pixels,
+    start_width: f32,
+}
+
+/// Sort order and column widths of a table, backed by `TableLayoutConfig`
+pub struct TableLayout {
+    columns: &'static [Column],
+    widths: HashMap<&'static str, f32>,
+    sort: Option<(&'static str, SortDirection)>,
+    drag: Option<ColumnDrag>,
+}
+
+impl TableLayout {
+    pub fn new(columns: &'static [Column], config: &TableLayoutConfig) -> Self {
+        let widths = columns
+            .iter()
+            .filter_map(|column| {
+                let default = column.width?;
+                let width = config
+                    .column_widths
+                    .get(column.id)
+                    .copied()
+                    .unwrap_or(default);
+                Some((column.id, width.max(MIN_COLUMN_WIDTH)))
+            })
+            .collect();
+
+        let sort = config.sort_column.as_deref().and_then(|id| {
+            let column = columns.iter().find(|c| c.id == id && c.sortable)?;
+            let direction = if config.sort_descending {
+                SortDirection::Descending
+            } else {
+                SortDirection::Ascending
+            };
+            Some((column.id, direction))
+        });
+
+        Self {
+            columns,
+            widths,
+            sort,
+            drag: None,
+        }
+    }
+
+    pub fn to_config(&self) -> TableLayoutConfig {
+        TableLayoutConfig {
+            sort_column: self.sort.map(|(id, _)| id.to_string()),
+            sort_descending: matches!(self.sort, Some((_, SortDirection::Descending))),
+            column_widths: self
+                .widths
+                .iter()
+                .map(|(id, width)| (id.to_string(), *width))
+                .collect(),
+        }
+    }
+
+    pub fn sort(&self) -> Option<(&'static str, SortDirection)> {
+        self.sort
+    }
+
+    /// Sort by a column, then flip the direction, then go back to the unsorted order
+    pub fn toggle_sort(&mut self, column: &'static str) {
+        self.sort = match self.sort {
+            Some((id, SortDirection::Ascending)) if id == column => {
+                Some((column, SortDirection::Descending))
+            }
+            Some((id, SortDirection::Descending)) if id == column => None,
+            _ => Some((column, SortDirection::Ascending)),
+        };
+    }
+
+    pub fn start_drag(&mut self, column: &'static str, x: Pixels) {
+        if let Some(width) = self.widths.get(column) {
+            self.drag = Some(ColumnDrag {
+                column,
+                start_x: x,
+                start_width: *width,
+            });
+        }
+    }
+
+    /// Resize the dragged column, returning whether anything changed
+    pub fn drag_to(&mut self, x: Pixels) -> bool {
+        let Some(drag) = &self.drag else {
+            return false;
+        };
+        let width = (drag.start_width + f32::from(x - drag.start_x)).max(MIN_COLUMN_WIDTH);
+        self.widths.insert(drag.column, width) != Some(width)
+    }
+
+    /// Finish a drag, returning whether one was in progress
+    pub fn end_drag(&mut self) -> bool {
+        self.drag.take().is_some()
+    }
+
+    /// Cell sized for a column, fixed width or filling the remaining space
+    pub fn cell(&self, column: &'static str) -> Div {
+        match self.widths.get(column) {
+            Some(width) => div().w(px(*width)).flex_shrink_0(),
+            None => div().flex_1(),
+        }
+        .text_sm()
+        .overflow_hidden()
+    }
+}
+
+/// A table view whose header is rendered from a `TableLayout`
+pub trait LayoutTable: Sized + 'static {
+    fn layout(&mut self) -> &mut TableLayout;
+
+    /// Called after the sort or a column width changed
+    fn layout_changed(&mut self, cx: &mut Context<Self>);
+}
+
+/// Header row with clickable sort labels and drag handles between columns, with an
+/// optional cell before the columns such as a select-all checkbox
+pub fn render_header<T: LayoutTable>(
+    layout: &TableLayout,
+    leading: Option<AnyElement>,
+    cx: &mut Context<T>,
+) -> impl IntoElement {
+    let cells: Vec<_> = layout
+        .columns
+        .iter()
+        .map(|column| {
+            let id = column.id;
+            let sort = layout.sort.filter(|(sorted, _)| *sorted == id);
+            let resizable = layout.widths.contains_key(id);
+
+            layout
+                .cell(id)
+                .id(SharedString::from(format!("column-{}", id)))
+                .relative()
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(column.label)
+                        .when_some(sort, |this, (_, direction)| {
+                            this.child(
+                                Icon::new(match direction {
+                                    SortDirection::Ascending => IconName::ChevronUp,
+                                    SortDirection::Descending => IconName::ChevronDown,
+                                })
+                                .xsmall(),
+                            )
+                        }),
+                )
+                .when(column.sortable, |this| {
+                    this.cursor_pointer()
+                        .on_click(cx.listener(move |this, _, _window, cx| {
+                            this.layout().toggle_sort(id);
+                            this.layout_changed(cx);
+                        }))
+                })
+                .when(resizable, |this| {
+                    this.child(
+                        div()
+                            .id(SharedString::from(format!("resize-{}", id)))
+                            .absolute()
+                            .top_0()
+                            .bottom_0()
+                            .right_0()
+                            .w(px(6.))
+                            .cursor(CursorStyle::ResizeLeftRight)
+                            .border_r_1()
+                            .border_color(cx.theme().border)
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                                    this.layout().start_drag(id, event.position.x);
+                                    cx.stop_propagation();
+                                }),
+                            ),
+                    )
+                })
+        })
+        .collect();
+
+    h_flex()
+        .w_full()
+        .px_3()
+        .py_2()
+        .border_b_1()
+        .border_color(cx.theme().border)
+        .bg(cx.theme().muted)
+        .children(leading)
+        .children(cells)
+}
+
+/// Follow the mouse while a column is being resized
+pub fn track_resize<T: LayoutTable>(element: Stateful<Div>, cx: &mut Context<T>) -> Stateful<Div> {
+    element
+        .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+            if this.layout().drag_to(event.position.x) {
+                cx.notify();
+            }
+        }))
+        .on_mouse_up(
+            MouseButton::Left,
+            cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
+                if this.layout().end_drag() {
+                    this.layout_changed(cx);
+                }
+            }),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[Column] = &[
+        Column {
+            id: "name",
+            label: "Name",
+            width: None,
+            sortable: true,
+        },
+        Column {
+            id: "modified",
+            label: "Modified",
+            width: Some(160.),
+            sortable: true,
+        },
+    ];
+
+    #[test]
+    fn test_toggle_sort_flips_direction_then_clears() {
+        let mut layout = TableLayout::new(COLUMNS, &TableLayoutConfig::default());
+        assert_eq!(layout.sort(), None);
+
+        layout.toggle_sort("name");
+        assert_eq!(layout.sort(), Some(("name", SortDirection::Ascending)));
+        layout.toggle_sort("name");
+        assert_eq!(layout.sort(), Some(("name", SortDirection::Descending)));
+        layout.toggle_sort("name");
+        assert_eq!(layout.sort(), None);
+        assert_eq!(layout.to_config().sort_column, None);
+
+        layout.toggle_sort("name");
+        layout.toggle_sort("modified");
+        assert_eq!(layout.sort(), Some(("modified", SortDirection::Ascending)));
+    }
+
+    #[test]
+    fn test_drag_respects_minimum_width() {
+        let mut layout = TableLayout::new(COLUMNS, &TableLayoutConfig::default());
+
+        layout.start_drag("modified", px(500.));
+        assert!(layout.drag_to(px(540.)));
+        assert_eq!(layout.to_config().column_widths["modified"], 200.);
+
+        layout.drag_to(px(0.));
+        assert!(layout.end_drag());
+        assert_eq!(
+            layout.to_config().column_widths["modified"],
+            MIN_COLUMN_WIDTH
+        );
+    }
+
+    #[test]
+    fn test_config_roundtrip_ignores_unknown_columns() {
+        let mut layout = TableLayout::new(COLUMNS, &TableLayoutConfig::default());
+        layout.toggle_sort("modified");
+        layout.toggle_sort("modified");
+
+        let mut config = layout.to_config();
+        config.column_widths.insert("gone".to_string(), 90.);
+        let restored = TableLayout::new(COLUMNS, &config);
+
+        assert_eq!(
+            restored.sort(),
+            Some(("modified", SortDirection::Descending))
+        );
+        assert!(!restored.to_config().column_widths.contains_key("gone"));
+    }
+}
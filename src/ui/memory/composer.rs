@@ -0,0 +1,261 @@
+use crate::memory::{MemoryType, Visibility};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    form::{field, v_form},
+    h_flex,
+    input::{Input, InputState},
+    select::{Select, SelectState},
+    v_flex, ActiveTheme, Disableable, IconName, IndexPath, Sizable,
+};
+
+use super::parse_list;
+
+#[derive(Debug, Clone)]
+pub enum MemoryComposerEvent {
+    Submit {
+        content: String,
+        memory_type: MemoryType,
+        tags: Vec<String>,
+        visibility: Visibility,
+        shared_with: Vec<String>,
+    },
+    Close,
+}
+
+impl EventEmitter<MemoryComposerEvent> for MemoryComposer {}
+
+/// Form for creating a new memory in Berry
+pub struct MemoryComposer {
+    content_input: Entity<InputState>,
+    tags_input: Entity<InputState>,
+    shared_with_input: Entity<InputState>,
+    type_select: Entity<SelectState<Vec<String>>>,
+    visibility_select: Entity<SelectState<Vec<String>>>,
+    submitting: bool,
+    error: Option<String>,
+}
+
+impl MemoryComposer {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let content_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .placeholder("What should be remembered?")
+        });
+
+        let tags_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Comma separated tags..."));
+
+        let shared_with_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Comma separated actor IDs..."));
+
+        let type_names: Vec<String> = MemoryType::all().iter().map(|t| t.to_string()).collect();
+        let default_type_index = MemoryType::all()
+            .iter()
+            .position(|t| *t == MemoryType::Information)
+            .map(IndexPath::new);
+        let type_select = cx.new(|cx| SelectState::new(type_names, default_type_index, window, cx));
+
+        let visibility_names: Vec<String> =
+            Visibility::all().iter().map(|v| v.to_string()).collect();
+        let default_visibility_index = Visibility::all()
+            .iter()
+            .position(|v| *v == Visibility::default())
+            .map(IndexPath::new);
+        let visibility_select =
+            cx.new(|cx| SelectState::new(visibility_names, default_visibility_index, window, cx));
+
+        // Re-render when visibility changes so the shared with field can be shown
+        cx.observe(&visibility_select, |_this, _select, cx| cx.notify())
+            .detach();
+
+        Self {
+            content_input,
+            tags_input,
+            shared_with_input,
+            type_select,
+            visibility_select,
+            submitting: false,
+            error: None,
+        }
+    }
+
+    /// Clear the form so it is ready for the next memory
+    pub fn reset(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for input in [
+            &self.content_input,
+            &self.tags_input,
+            &self.shared_with_input,
+        ] {
+            input.update(cx, |state, cx| {
+                state.set_value("", window, cx);
+            });
+        }
+        self.submitting = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.submitting = false;
+        self.error = error;
+    }
+
+    fn selected_type(&self, cx: &App) -> MemoryType {
+        self.type_select
+            .read(cx)
+            .selected_value()
+            .and_then(|name| MemoryType::all().iter().find(|t| t.to_string() == *name))
+            .cloned()
+            .unwrap_or(MemoryType::Information)
+    }
+
+    fn selected_visibility(&self, cx: &App) -> Visibility {
+        self.visibility_select
+            .read(cx)
+            .selected_value()
+            .and_then(|name| Visibility::all().iter().find(|v| v.to_string() == *name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn submit(&mut self, cx: &mut Context<Self>) {
+        if self.submitting {
+            return;
+        }
+
+        let content = self.content_input.read(cx).text().to_string();
+        if content.trim().is_empty() {
+            self.error = Some("Content cannot be empty".to_string());
+            cx.notify();
+            return;
+        }
+
+        let visibility = self.selected_visibility(cx);
+        let shared_with = if visibility == Visibility::Shared {
+            parse_list(&self.shared_with_input.read(cx).text().to_string())
+        } else {
+            vec![]
+        };
+
+        if visibility == Visibility::Shared && shared_with.is_empty() {
+            self.error = Some("Shared memories need at least one actor".to_string());
+            cx.notify();
+            return;
+        }
+
+        self.submitting = true;
+        self.error = None;
+        cx.emit(MemoryComposerEvent::Submit {
+            content: content.trim().to_string(),
+            memory_type: self.selected_type(cx),
+            tags: parse_list(&self.tags_input.read(cx).text().to_string()),
+            visibility,
+            shared_with,
+        });
+        cx.notify();
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_4()
+            .py_3()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .justify_between()
+            .child(
+                div()
+                    .text_base()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child("New Memory"),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("submit-memory")
+                            .label(if self.submitting {
+                                "Saving..."
+                            } else {
+                                "Remember"
+                            })
+                            .primary()
+                            .small()
+                            .disabled(self.submitting)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.submit(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("close-composer")
+                            .icon(IconName::Close)
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|_this, _, _window, cx| {
+                                cx.emit(MemoryComposerEvent::Close);
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for MemoryComposer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_shared = self.selected_visibility(cx) == Visibility::Shared;
+
+        let form = v_form()
+            .child(
+                field()
+                    .label("Content")
+                    .child(Input::new(&self.content_input).w_full().h(px(200.))),
+            )
+            .child(
+                field()
+                    .label("Type")
+                    .child(Select::new(&self.type_select).w(px(200.))),
+            )
+            .child(
+                field()
+                    .label("Tags")
+                    .description("For example: assignment, mentor, staff")
+                    .child(Input::new(&self.tags_input).w_full()),
+            )
+            .child(
+                field()
+                    .label("Visibility")
+                    .child(Select::new(&self.visibility_select).w(px(200.))),
+            )
+            .when(is_shared, |this| {
+                this.child(
+                    field()
+                        .label("Shared With")
+                        .description("Actors that can see this memory")
+                        .child(Input::new(&self.shared_with_input).w_full()),
+                )
+            });
+
+        v_flex()
+            .w(px(600.))
+            .h_full()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().background)
+            .child(self.render_header(cx))
+            .child(
+                v_flex()
+                    .id("composer-content")
+                    .w_full()
+                    .flex_1()
+                    .p_4()
+                    .gap_4()
+                    .overflow_y_scroll()
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                    })
+                    .child(form),
+            )
+    }
+}
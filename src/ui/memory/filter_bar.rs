@@ -0,0 +1,213 @@
+use crate::memory::{Memory, MemoryType, SearchRequest};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex, v_flex, ActiveTheme, Selectable, Sizable,
+};
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone)]
+pub enum MemoryFilterBarEvent {
+    Changed,
+}
+
+impl EventEmitter<MemoryFilterBarEvent> for MemoryFilterBar {}
+
+/// Filters applied on top of the free text search query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryFilters {
+    pub memory_type: Option<MemoryType>,
+    pub tags: Vec<String>,
+    pub created_by: Option<String>,
+}
+
+impl MemoryFilters {
+    pub fn is_empty(&self) -> bool {
+        self.memory_type.is_none() && self.tags.is_empty() && self.created_by.is_none()
+    }
+
+    pub fn apply(&self, request: &mut SearchRequest) {
+        request.memory_type = self.memory_type.clone();
+        request.tags = (!self.tags.is_empty()).then(|| self.tags.clone());
+        request.created_by = self.created_by.clone();
+    }
+}
+
+/// Facets for type, tags and author built from the memories loaded so far
+#[derive(Default)]
+pub struct MemoryFilterBar {
+    filters: MemoryFilters,
+    types: Vec<MemoryType>,
+    tags: BTreeSet<String>,
+    authors: BTreeSet<String>,
+}
+
+impl MemoryFilterBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filters(&self) -> &MemoryFilters {
+        &self.filters
+    }
+
+    /// Add facet options from newly loaded memories
+    pub fn add_options(&mut self, memories: &[Memory]) {
+        for memory in memories {
+            if !self.types.contains(&memory.memory_type) {
+                self.types.push(memory.memory_type.clone());
+            }
+            self.tags.extend(memory.tags.iter().cloned());
+            self.authors.insert(memory.created_by.clone());
+        }
+    }
+
+    fn set_type(&mut self, memory_type: Option<MemoryType>, cx: &mut Context<Self>) {
+        self.filters.memory_type = memory_type;
+        self.changed(cx);
+    }
+
+    fn toggle_tag(&mut self, tag: &str, cx: &mut Context<Self>) {
+        if let Some(index) = self.filters.tags.iter().position(|t| t == tag) {
+            self.filters.tags.remove(index);
+        } else {
+            self.filters.tags.push(tag.to_string());
+        }
+        self.changed(cx);
+    }
+
+    fn set_author(&mut self, author: Option<String>, cx: &mut Context<Self>) {
+        self.filters.created_by = author;
+        self.changed(cx);
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.filters = MemoryFilters::default();
+        self.changed(cx);
+    }
+
+    fn changed(&mut self, cx: &mut Context<Self>) {
+        cx.emit(MemoryFilterBarEvent::Changed);
+        cx.notify();
+    }
+
+    fn render_facet(
+        &self,
+        label: &'static str,
+        chips: Vec<Button>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .gap_2()
+            .items_start()
+            .child(
+                div()
+                    .w(px(60.))
+                    .pt_0p5()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(label),
+            )
+            .child(h_flex().flex_1().flex_wrap().gap_1().children(chips))
+    }
+
+    fn chip(id: impl Into<ElementId>, label: impl Into<SharedString>, selected: bool) -> Button {
+        Button::new(id)
+            .label(label)
+            .xsmall()
+            .outline()
+            .selected(selected)
+    }
+}
+
+impl Render for MemoryFilterBar {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let all_types = Self::chip("filter-type-all", "All", self.filters.memory_type.is_none())
+            .on_click(cx.listener(|this, _, _window, cx| {
+                this.set_type(None, cx);
+            }));
+        let mut type_chips = vec![all_types];
+        for (index, memory_type) in self.types.iter().enumerate() {
+            let value = memory_type.clone();
+            type_chips.push(
+                Self::chip(
+                    ("filter-type", index),
+                    memory_type.to_string(),
+                    self.filters.memory_type.as_ref() == Some(memory_type),
+                )
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.set_type(Some(value.clone()), cx);
+                })),
+            );
+        }
+
+        let tag_chips: Vec<Button> = self
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(index, tag)| {
+                let value = tag.clone();
+                Self::chip(
+                    ("filter-tag", index),
+                    tag.clone(),
+                    self.filters.tags.contains(tag),
+                )
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.toggle_tag(&value, cx);
+                }))
+            })
+            .collect();
+
+        let all_authors = Self::chip(
+            "filter-author-all",
+            "All",
+            self.filters.created_by.is_none(),
+        )
+        .on_click(cx.listener(|this, _, _window, cx| {
+            this.set_author(None, cx);
+        }));
+        let mut author_chips = vec![all_authors];
+        for (index, author) in self.authors.iter().enumerate() {
+            let value = author.clone();
+            author_chips.push(
+                Self::chip(
+                    ("filter-author", index),
+                    author.clone(),
+                    self.filters.created_by.as_ref() == Some(author),
+                )
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.set_author(Some(value.clone()), cx);
+                })),
+            );
+        }
+
+        v_flex()
+            .id("memory-filter-bar")
+            .w_full()
+            .px_4()
+            .py_2()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(self.render_facet("Type", type_chips, cx))
+            .when(!tag_chips.is_empty(), |this| {
+                this.child(self.render_facet("Tags", tag_chips, cx))
+            })
+            .child(self.render_facet("Author", author_chips, cx))
+            .when(!self.filters.is_empty(), |this| {
+                this.child(
+                    h_flex().w_full().justify_end().child(
+                        Button::new("clear-filters")
+                            .label("Clear Filters")
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.clear(cx);
+                            })),
+                    ),
+                )
+            })
+    }
+}
@@ -0,0 +1,93 @@
+use crate::memory::{BerryClient, BerryError, BerryStatus};
+use gpui::*;
+use gpui_tokio_bridge::{JoinError, Tokio};
+use std::time::Duration;
+
+/// How often the Berry server is checked in the background
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Polls the Berry `/health` endpoint and keeps the latest status
+pub struct BerryHealthMonitor {
+    client: BerryClient,
+    status: BerryStatus,
+    checking: bool,
+    /// Incremented when the client changes so checks against the old server are dropped
+    generation: usize,
+    _poll_task: Task<()>,
+}
+
+impl BerryHealthMonitor {
+    pub fn new(client: BerryClient, cx: &mut Context<Self>) -> Self {
+        let poll_task = cx.spawn(async move |this, cx| loop {
+            if this.update(cx, |this, cx| this.check_now(cx)).is_err() {
+                break;
+            }
+            cx.background_executor().timer(POLL_INTERVAL).await;
+        });
+
+        Self {
+            client,
+            status: BerryStatus::Unknown,
+            checking: false,
+            generation: 0,
+            _poll_task: poll_task,
+        }
+    }
+
+    pub fn status(&self) -> &BerryStatus {
+        &self.status
+    }
+
+    pub fn server_url(&self) -> &str {
+        self.client.base_url()
+    }
+
+    /// Check a different server from now on, starting with an immediate check
+    pub fn set_client(&mut self, client: BerryClient, cx: &mut Context<Self>) {
+        self.client = client;
+        self.generation += 1;
+        self.checking = false;
+        self.status = BerryStatus::Unknown;
+        cx.notify();
+        self.check_now(cx);
+    }
+
+    /// Run a health check immediately, ignored if one is already in flight
+    pub fn check_now(&mut self, cx: &mut Context<Self>) {
+        if self.checking {
+            return;
+        }
+        self.checking = true;
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let generation = self.generation;
+
+        let task = Tokio::spawn(cx, async move { client.health().await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<Duration, BerryError>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    if this.generation != generation {
+                        return;
+                    }
+                    this.checking = false;
+                    let status = match result {
+                        Ok(Ok(latency)) => BerryStatus::Online { latency },
+                        Ok(Err(e)) if e.is_auth_failure() => {
+                            BerryStatus::Unauthorized(e.to_string())
+                        }
+                        Ok(Err(e)) => BerryStatus::Offline(e.to_string()),
+                        Err(e) => BerryStatus::Offline(e.to_string()),
+                    };
+                    // Observers react to transitions themselves, the footer shows
+                    // the latency of every check
+                    this.status = status;
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+}
@@ -1,19 +1,81 @@
-use crate::knowledgebase::{self, KnowledgebaseFile};
+use crate::knowledgebase::{self, DiffLine, DiskChange, Frontmatter, KnowledgebaseFile};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
     button::{Button, ButtonVariants},
     h_flex,
     input::{Input, InputState},
-    v_flex, ActiveTheme, IconName, Sizable,
+    select::{Select, SelectState},
+    text::TextView,
+    v_flex, ActiveTheme, IconName, IndexPath, Selectable, Sizable,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use super::knowledgebase_view::PersonaTab;
+use super::parse_list;
+
+#[derive(Debug, Clone)]
+pub enum KnowledgebaseSlideoutEvent {
+    Rename { path: PathBuf, new_name: String },
+    Duplicate { path: PathBuf },
+    Move { path: PathBuf, kb_path: PathBuf },
+    Delete { path: PathBuf },
+}
+
+impl EventEmitter<KnowledgebaseSlideoutEvent> for KnowledgebaseSlideout {}
+
+/// File action waiting for more input under the header
+enum FileAction {
+    Rename(Entity<InputState>),
+    /// Knowledgebase the file is moved into, listed by persona name
+    Move(Entity<SelectState<Vec<String>>>),
+    Details(DetailsForm),
+}
+
+/// Frontmatter fields of the open document being edited
+struct DetailsForm {
+    status_input: Entity<InputState>,
+    /// Comma separated
+    tags_input: Entity<InputState>,
+    persona_input: Entity<InputState>,
+    summary_input: Entity<InputState>,
+}
+
+/// The open file changed on disk since it was opened or last saved
+struct Conflict {
+    change: DiskChange,
+    /// Lines from the disk version to the editor's, while the diff is shown
+    diff: Option<Vec<DiffLine>>,
+}
+
+/// How the open document is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewMode {
+    /// Rendered Markdown only, as most documents are read rather than edited
+    Preview,
+    Edit,
+    /// Editor and preview side by side, the preview following the editor's scrolling
+    Split,
+}
+
 pub struct KnowledgebaseSlideout {
     file: Option<KnowledgebaseFile>,
     file_path: Option<PathBuf>,
     editor_state: Option<Entity<InputState>>,
+    /// Keeps the preview in step with the editor while it is open
+    _editor_subscription: Option<Subscription>,
+    mode: ViewMode,
+    preview_scroll: ScrollHandle,
+    /// Editor scroll offset the preview last followed, so it can still be
+    /// scrolled by hand until the editor moves
+    synced_editor_offset: Option<Point<Pixels>>,
+    /// Persona knowledgebases files can be moved into
+    knowledgebases: Vec<PersonaTab>,
+    action: Option<FileAction>,
+    confirm_delete: bool,
+    conflict: Option<Conflict>,
+    error: Option<String>,
     on_close: Arc<dyn Fn(&mut Window, &mut App) + Send + Sync + 'static>,
     save_status: Option<SaveStatus>,
 }
@@ -26,7 +88,7 @@ enum SaveStatus {
 }
 
 impl KnowledgebaseSlideout {
-    pub fn new<F>(on_close: F) -> Self
+    pub fn new<F>(knowledgebases: Vec<PersonaTab>, on_close: F) -> Self
     where
         F: Fn(&mut Window, &mut App) + Send + Sync + 'static,
     {
@@ -34,6 +96,15 @@ impl KnowledgebaseSlideout {
             file: None,
             file_path: None,
             editor_state: None,
+            _editor_subscription: None,
+            mode: ViewMode::Preview,
+            preview_scroll: ScrollHandle::new(),
+            synced_editor_offset: None,
+            knowledgebases,
+            action: None,
+            confirm_delete: false,
+            conflict: None,
+            error: None,
             on_close: Arc::new(on_close),
             save_status: None,
         }
@@ -47,6 +118,10 @@ impl KnowledgebaseSlideout {
     ) {
         self.file_path = file.as_ref().map(|f| f.entry.file_path.clone());
         self.save_status = None;
+        self.action = None;
+        self.confirm_delete = false;
+        self.conflict = None;
+        self.error = None;
 
         if let Some(file) = &file {
             let editor = cx.new(|cx| {
@@ -55,30 +130,107 @@ impl KnowledgebaseSlideout {
                     .line_number(true)
                     .default_value(&file.content)
             });
+            self._editor_subscription = Some(cx.observe(&editor, |this, _editor, cx| {
+                this.sync_preview_scroll(cx);
+                cx.notify();
+            }));
             self.editor_state = Some(editor);
         } else {
+            self._editor_subscription = None;
             self.editor_state = None;
         }
+        self.preview_scroll.set_offset(point(px(0.), px(0.)));
+        self.synced_editor_offset = None;
 
         self.file = file;
     }
 
+    /// Save the editor's content unless the file changed on disk since it was
+    /// opened, in which case the conflict is shown instead
     fn save_file(&mut self, cx: &mut Context<Self>) {
-        let Some(path) = &self.file_path else {
+        let (Some(path), Some(file), Some(editor)) =
+            (&self.file_path, &self.file, &self.editor_state)
+        else {
             return;
         };
-        let Some(editor) = &self.editor_state else {
+
+        let content = editor.read(cx).text().to_string();
+        match knowledgebase::changed_on_disk(path, &file.content) {
+            Ok(None) => {}
+            // Someone else made the same edit, so there is nothing to lose
+            Ok(Some(DiskChange::Modified(disk))) if disk == content => {}
+            Ok(Some(change)) => {
+                self.conflict = Some(Conflict { change, diff: None });
+                self.save_status = None;
+                cx.notify();
+                return;
+            }
+            Err(e) => {
+                self.save_status = Some(SaveStatus::Error(e.to_string()));
+                cx.notify();
+                return;
+            }
+        }
+
+        self.write_file(content, cx);
+    }
+
+    /// Whether the editor differs from the file as it was opened or last saved
+    pub fn has_unsaved_edits(&self, cx: &App) -> bool {
+        match (&self.file, &self.editor_state) {
+            (Some(file), Some(editor)) => editor.read(cx).text().to_string() != file.content,
+            _ => false,
+        }
+    }
+
+    /// Save unsaved edits before the file is moved or another one is opened
+    ///
+    /// Returns false if they couldn't be saved, the conflict or error being shown
+    /// instead so they can be overwritten or discarded.
+    pub fn save_edits(&mut self, cx: &mut Context<Self>) -> bool {
+        if !self.has_unsaved_edits(cx) {
+            return true;
+        }
+        self.save_file(cx);
+        !self.has_unsaved_edits(cx)
+    }
+
+    /// Put the editor back to the file as it was opened or last saved
+    fn discard_edits(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(file), Some(editor)) = (&self.file, &self.editor_state) else {
             return;
         };
+        let content = file.content.clone();
+        editor.update(cx, |state, cx| state.set_value(content, window, cx));
+        self.conflict = None;
+        self.save_status = None;
+        cx.notify();
+    }
 
+    /// Save the editor's content over whatever is on disk
+    fn overwrite(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = &self.editor_state else {
+            return;
+        };
         let content = editor.read(cx).text().to_string();
-        let path = path.clone();
+        self.write_file(content, cx);
+    }
+
+    fn write_file(&mut self, content: String, cx: &mut Context<Self>) {
+        let (Some(path), Some(file)) = (self.file_path.clone(), &self.file) else {
+            return;
+        };
+        let kb_path = file.kb_path.clone();
 
         self.save_status = Some(SaveStatus::Saving);
         cx.notify();
 
-        match knowledgebase::save_file(&path, &content) {
+        match knowledgebase::save_file(&kb_path, &path, &content) {
             Ok(()) => {
+                if let Some(file) = &mut self.file {
+                    file.content = content;
+                }
+                self.conflict = None;
                 self.save_status = Some(SaveStatus::Saved);
                 cx.notify();
             }
@@ -89,9 +241,532 @@ impl KnowledgebaseSlideout {
         }
     }
 
+    /// Replace the editor's content with the version on disk, dropping unsaved edits
+    fn reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(Conflict {
+            change: DiskChange::Modified(disk),
+            ..
+        }) = &self.conflict
+        else {
+            return;
+        };
+        let disk = disk.clone();
+        self.conflict = None;
+
+        if let Some(editor) = &self.editor_state {
+            editor.update(cx, |state, cx| state.set_value(disk.clone(), window, cx));
+        }
+        if let Some(file) = &mut self.file {
+            file.content = disk;
+        }
+        self.save_status = None;
+        cx.notify();
+    }
+
+    /// Look at the open file again after it changed on disk
+    ///
+    /// Without unsaved edits the new version is loaded straight away, otherwise
+    /// the conflict is shown so the edits aren't lost.
+    pub fn check_disk(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(path), Some(file), Some(editor)) =
+            (&self.file_path, &self.file, &self.editor_state)
+        else {
+            return;
+        };
+
+        let Ok(change) = knowledgebase::changed_on_disk(path, &file.content) else {
+            return;
+        };
+        let edited = editor.read(cx).text().to_string() != file.content;
+
+        match change {
+            None => self.conflict = None,
+            Some(change @ DiskChange::Modified(_)) if !edited => {
+                self.conflict = Some(Conflict { change, diff: None });
+                self.reload(window, cx);
+            }
+            Some(change) => {
+                let show_diff = self.conflict.as_ref().is_some_and(|c| c.diff.is_some());
+                self.conflict = Some(Conflict { change, diff: None });
+                if show_diff {
+                    self.toggle_diff(cx);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn toggle_diff(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = &self.editor_state else {
+            return;
+        };
+        let content = editor.read(cx).text().to_string();
+        let Some(conflict) = &mut self.conflict else {
+            return;
+        };
+
+        conflict.diff = match (&conflict.diff, &conflict.change) {
+            (Some(_), _) => None,
+            (None, DiskChange::Modified(disk)) => Some(knowledgebase::diff_lines(disk, &content)),
+            (None, DiskChange::Removed) => Some(knowledgebase::diff_lines("", &content)),
+        };
+        cx.notify();
+    }
+
+    fn render_conflict(&self, conflict: &Conflict, cx: &mut Context<Self>) -> impl IntoElement {
+        let message = match conflict.change {
+            DiskChange::Modified(_) => "This file changed on disk since it was opened.",
+            DiskChange::Removed => "This file was removed from disk since it was opened.",
+        };
+
+        h_flex()
+            .w_full()
+            .px_4()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().warning.opacity(0.15))
+            .child(div().flex_1().text_sm().child(message))
+            .when(matches!(conflict.change, DiskChange::Modified(_)), |this| {
+                this.child(
+                    Button::new("reload-kb-file")
+                        .label("Reload")
+                        .small()
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.reload(window, cx);
+                        })),
+                )
+            })
+            .child(
+                Button::new("overwrite-kb-file")
+                    .label("Overwrite")
+                    .danger()
+                    .small()
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.overwrite(cx);
+                    })),
+            )
+            .child(
+                Button::new("diff-kb-file")
+                    .label(if conflict.diff.is_some() {
+                        "Hide Diff"
+                    } else {
+                        "Show Diff"
+                    })
+                    .ghost()
+                    .small()
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.toggle_diff(cx);
+                    })),
+            )
+    }
+
+    fn render_diff(&self, lines: &[DiffLine], cx: &mut Context<Self>) -> impl IntoElement {
+        let rows: Vec<_> = lines
+            .iter()
+            .map(|line| {
+                let (marker, text, color) = match line {
+                    DiffLine::Same(text) => (" ", text, None),
+                    DiffLine::Removed(text) => ("-", text, Some(cx.theme().danger)),
+                    DiffLine::Added(text) => ("+", text, Some(cx.theme().success)),
+                };
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .px_2()
+                    .when_some(color, |this, color| this.bg(color.opacity(0.15)))
+                    .child(div().w(px(12.)).child(marker))
+                    .child(div().flex_1().child(text.clone()))
+            })
+            .collect();
+
+        v_flex()
+            .id("kb-diff-content")
+            .w_full()
+            .flex_1()
+            .p_4()
+            .gap_2()
+            .overflow_y_scroll()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("- on disk    + your edits"),
+            )
+            .child(
+                v_flex()
+                    .w_full()
+                    .font_family("monospace")
+                    .text_sm()
+                    .children(rows),
+            )
+    }
+
+    fn set_mode(&mut self, mode: ViewMode, cx: &mut Context<Self>) {
+        self.mode = mode;
+        self.synced_editor_offset = None;
+        self.sync_preview_scroll(cx);
+        cx.notify();
+    }
+
+    /// In split mode, scroll the preview to the same fraction of the document as
+    /// the editor is scrolled to, only once the editor has moved
+    fn sync_preview_scroll(&mut self, cx: &App) {
+        if self.mode != ViewMode::Split {
+            return;
+        }
+        let Some(editor) = &self.editor_state else {
+            return;
+        };
+
+        let editor_scroll = editor.read(cx).scroll_handle().clone();
+        let offset = editor_scroll.offset();
+        if self.synced_editor_offset == Some(offset) {
+            return;
+        }
+        self.synced_editor_offset = Some(offset);
+
+        let editor_max = editor_scroll.max_offset().height;
+        let fraction = if editor_max > px(0.) {
+            (-offset.y / editor_max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let max_offset = self.preview_scroll.max_offset();
+        self.preview_scroll
+            .set_offset(point(px(0.), -max_offset.height * fraction));
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
+    /// Knowledgebases other than the one holding the open file
+    fn move_targets(&self) -> Vec<&PersonaTab> {
+        let Some(path) = &self.file_path else {
+            return vec![];
+        };
+        self.knowledgebases
+            .iter()
+            .filter(|kb| !path.starts_with(&kb.kb_path))
+            .collect()
+    }
+
+    fn start_rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(file) = &self.file else {
+            return;
+        };
+
+        let current = file.entry.relative_path.display().to_string();
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("New name or folder/name")
+                .default_value(current)
+        });
+        self.action = Some(FileAction::Rename(input));
+        self.confirm_delete = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn start_move(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let names: Vec<String> = self
+            .move_targets()
+            .into_iter()
+            .map(|kb| kb.name.clone())
+            .collect();
+        let select = cx.new(|cx| SelectState::new(names, Some(IndexPath::new(0)), window, cx));
+
+        self.action = Some(FileAction::Move(select));
+        self.confirm_delete = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn start_details(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editor) = &self.editor_state else {
+            return;
+        };
+
+        let frontmatter = Frontmatter::from_document(&editor.read(cx).text().to_string());
+        let mut input = |placeholder: &'static str, value: String| {
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder)
+                    .default_value(value)
+            })
+        };
+        let form = DetailsForm {
+            status_input: input("draft, final, ...", frontmatter.status.unwrap_or_default()),
+            tags_input: input("tag1, tag2", frontmatter.tags.join(", ")),
+            persona_input: input("Related persona", frontmatter.persona.unwrap_or_default()),
+            summary_input: input("One line summary", frontmatter.summary.unwrap_or_default()),
+        };
+
+        self.action = Some(FileAction::Details(form));
+        self.confirm_delete = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    /// Rewrite the frontmatter in the editor from the details form and save,
+    /// leaving the rest of the document as it is
+    fn save_details(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(editor), Some(FileAction::Details(form))) = (&self.editor_state, &self.action)
+        else {
+            return;
+        };
+
+        let optional = |input: &Entity<InputState>| {
+            let value = input.read(cx).text().trim().to_string();
+            (!value.is_empty()).then_some(value)
+        };
+        let content = editor.read(cx).text().to_string();
+        let frontmatter = Frontmatter {
+            tags: parse_list(&form.tags_input.read(cx).text().to_string()),
+            status: optional(&form.status_input),
+            summary: optional(&form.summary_input),
+            persona: optional(&form.persona_input),
+            // Keys the form doesn't show are kept
+            ..Frontmatter::from_document(&content)
+        };
+
+        match knowledgebase::with_frontmatter(&content, &frontmatter) {
+            Ok(content) => {
+                editor.update(cx, |state, cx| state.set_value(content, window, cx));
+                self.action = None;
+                self.error = None;
+                self.save_file(cx);
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                cx.notify();
+            }
+        }
+    }
+
+    fn confirm_action(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if matches!(self.action, Some(FileAction::Details(_))) {
+            self.save_details(window, cx);
+            return;
+        }
+        let (Some(path), Some(action)) = (self.file_path.clone(), &self.action) else {
+            return;
+        };
+
+        let event = match action {
+            FileAction::Rename(input) => KnowledgebaseSlideoutEvent::Rename {
+                path,
+                new_name: input.read(cx).text().trim().to_string(),
+            },
+            FileAction::Move(select) => {
+                let selected = select.read(cx).selected_value().cloned();
+                let Some(knowledgebase) =
+                    selected.and_then(|name| self.knowledgebases.iter().find(|kb| kb.name == name))
+                else {
+                    self.error = Some("Choose a knowledgebase".to_string());
+                    cx.notify();
+                    return;
+                };
+                KnowledgebaseSlideoutEvent::Move {
+                    path,
+                    kb_path: knowledgebase.kb_path.clone(),
+                }
+            }
+            FileAction::Details(_) => return,
+        };
+
+        // The action works on the file on disk, so the edits have to be in it
+        if !self.save_edits(cx) {
+            return;
+        }
+        self.action = None;
+        cx.emit(event);
+        cx.notify();
+    }
+
+    fn duplicate(&mut self, cx: &mut Context<Self>) {
+        if let Some(path) = self.file_path.clone() {
+            if self.save_edits(cx) {
+                cx.emit(KnowledgebaseSlideoutEvent::Duplicate { path });
+            }
+        }
+    }
+
+    fn delete(&mut self, cx: &mut Context<Self>) {
+        let Some(path) = self.file_path.clone() else {
+            return;
+        };
+
+        if !self.confirm_delete {
+            self.confirm_delete = true;
+            self.action = None;
+            cx.notify();
+            return;
+        }
+
+        self.confirm_delete = false;
+        // Saved first so the edits can be recovered from the trash
+        if self.save_edits(cx) {
+            cx.emit(KnowledgebaseSlideoutEvent::Delete { path });
+        }
+    }
+
+    fn render_actions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let form = match &self.action {
+            Some(FileAction::Rename(input)) => {
+                Some(Input::new(input).small().w_full().into_any_element())
+            }
+            Some(FileAction::Move(select)) => {
+                Some(Select::new(select).small().w_full().into_any_element())
+            }
+            Some(FileAction::Details(form)) => {
+                Some(self.render_details_form(form, cx).into_any_element())
+            }
+            None => None,
+        };
+        let confirm_label = match &self.action {
+            Some(FileAction::Move(_)) => "Move",
+            Some(FileAction::Details(_)) => "Save",
+            _ => "Rename",
+        };
+
+        v_flex()
+            .w_full()
+            .px_4()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("rename-kb-file")
+                            .label("Rename")
+                            .small()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_rename(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("details-kb-file")
+                            .label("Details")
+                            .small()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_details(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("duplicate-kb-file")
+                            .label("Duplicate")
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.duplicate(cx);
+                            })),
+                    )
+                    .when(!self.move_targets().is_empty(), |this| {
+                        this.child(Button::new("move-kb-file").label("Move").small().on_click(
+                            cx.listener(|this, _, window, cx| {
+                                this.start_move(window, cx);
+                            }),
+                        ))
+                    })
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("delete-kb-file")
+                            .label(if self.confirm_delete {
+                                "Move to Trash"
+                            } else {
+                                "Delete"
+                            })
+                            .danger()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.delete(cx);
+                            })),
+                    )
+                    .when(self.confirm_delete, |this| {
+                        this.child(
+                            Button::new("cancel-delete-kb-file")
+                                .label("Cancel")
+                                .ghost()
+                                .small()
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.confirm_delete = false;
+                                    cx.notify();
+                                })),
+                        )
+                    }),
+            )
+            .when_some(form, |this, form| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .items_end()
+                        .child(div().flex_1().child(form))
+                        .child(
+                            Button::new("confirm-kb-file-action")
+                                .label(confirm_label)
+                                .primary()
+                                .small()
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.confirm_action(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("cancel-kb-file-action")
+                                .label("Cancel")
+                                .ghost()
+                                .small()
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.action = None;
+                                    cx.notify();
+                                })),
+                        ),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .w_full()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+    }
+
+    fn render_details_form(&self, form: &DetailsForm, cx: &mut Context<Self>) -> impl IntoElement {
+        let field = |label: &'static str, input: &Entity<InputState>| {
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .w(px(60.))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(label),
+                )
+                .child(div().flex_1().child(Input::new(input).small()))
+        };
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .child(field("Status", &form.status_input))
+            .child(field("Tags", &form.tags_input))
+            .child(field("Persona", &form.persona_input))
+            .child(field("Summary", &form.summary_input))
+    }
+
     fn render_header(&self, file: &KnowledgebaseFile, cx: &mut Context<Self>) -> impl IntoElement {
         let entity = cx.entity().clone();
         let entity_for_save = cx.entity().clone();
+        let edited = self.has_unsaved_edits(cx);
 
         h_flex()
             .w_full()
@@ -110,6 +785,14 @@ impl KnowledgebaseSlideout {
                             .font_weight(FontWeight::SEMIBOLD)
                             .child(file.entry.name.clone()),
                     )
+                    .when(!file.entry.folder().as_os_str().is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(file.entry.folder().display().to_string()),
+                        )
+                    })
                     .when_some(self.save_status.clone(), |this, status| {
                         let (text, color) = match status {
                             SaveStatus::Saving => ("Saving...", cx.theme().muted_foreground),
@@ -122,6 +805,39 @@ impl KnowledgebaseSlideout {
             .child(
                 h_flex()
                     .gap_2()
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(self.render_mode_button(
+                                "kb-mode-preview",
+                                "Preview",
+                                ViewMode::Preview,
+                                cx,
+                            ))
+                            .child(self.render_mode_button(
+                                "kb-mode-edit",
+                                "Edit",
+                                ViewMode::Edit,
+                                cx,
+                            ))
+                            .child(self.render_mode_button(
+                                "kb-mode-split",
+                                "Split",
+                                ViewMode::Split,
+                                cx,
+                            )),
+                    )
+                    .when(edited, |this| {
+                        this.child(
+                            Button::new("discard-kb-edits")
+                                .label("Discard")
+                                .ghost()
+                                .small()
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.discard_edits(window, cx);
+                                })),
+                        )
+                    })
                     .child(Button::new("save-kb").label("Save").small().on_click(
                         move |_, _window, cx| {
                             entity_for_save.update(cx, |this, cx| {
@@ -142,15 +858,24 @@ impl KnowledgebaseSlideout {
             )
     }
 
-    fn render_content(
+    fn render_mode_button(
         &self,
-        _file: &KnowledgebaseFile,
-        _cx: &mut Context<Self>,
+        id: &'static str,
+        label: &'static str,
+        mode: ViewMode,
+        cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let Some(editor) = &self.editor_state else {
-            return div().into_any_element();
-        };
+        Button::new(id)
+            .label(label)
+            .ghost()
+            .xsmall()
+            .selected(self.mode == mode)
+            .on_click(cx.listener(move |this, _, _window, cx| {
+                this.set_mode(mode, cx);
+            }))
+    }
 
+    fn render_editor(&self, editor: &Entity<InputState>) -> impl IntoElement {
         v_flex()
             .id("kb-editor-content")
             .w_full()
@@ -158,24 +883,116 @@ impl KnowledgebaseSlideout {
             .p_4()
             .overflow_hidden()
             .child(Input::new(editor).h_full().w_full())
-            .into_any_element()
+    }
+
+    /// The editor's content rendered as Markdown, so unsaved edits show up too
+    fn render_preview(
+        &self,
+        editor: &Entity<InputState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let content = editor.read(cx).text().to_string();
+        let frontmatter = Frontmatter::from_document(&content);
+        let details: Vec<String> = [
+            frontmatter.status.clone(),
+            (!frontmatter.tags.is_empty()).then(|| frontmatter.tags.join(", ")),
+            frontmatter.persona.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        v_flex()
+            .id("kb-preview-content")
+            .w_full()
+            .flex_1()
+            .p_4()
+            .gap_2()
+            .overflow_y_scroll()
+            .track_scroll(&self.preview_scroll)
+            .text_sm()
+            // Frontmatter is shown as a summary line rather than as Markdown
+            .when(!details.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(details.join(" · ")),
+                )
+            })
+            .when_some(frontmatter.summary.clone(), |this, summary| {
+                this.child(div().italic().child(summary))
+            })
+            .child(TextView::markdown(
+                "kb-preview",
+                knowledgebase::body(&content).to_string(),
+                window,
+                cx,
+            ))
+    }
+
+    fn render_content(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(editor) = &self.editor_state else {
+            return div().into_any_element();
+        };
+
+        match self.mode {
+            ViewMode::Preview => self.render_preview(editor, window, cx).into_any_element(),
+            ViewMode::Edit => self.render_editor(editor).into_any_element(),
+            ViewMode::Split => h_flex()
+                .w_full()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    div()
+                        .w_1_2()
+                        .h_full()
+                        .flex()
+                        .border_r_1()
+                        .border_color(cx.theme().border)
+                        .child(self.render_editor(editor)),
+                )
+                .child(
+                    div()
+                        .w_1_2()
+                        .h_full()
+                        .flex()
+                        .child(self.render_preview(editor, window, cx)),
+                )
+                .into_any_element(),
+        }
     }
 }
 
 impl Render for KnowledgebaseSlideout {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let Some(file) = &self.file else {
             return div().into_any_element();
         };
+        let width = if self.mode == ViewMode::Split {
+            px(1000.)
+        } else {
+            px(600.)
+        };
 
         v_flex()
-            .w(px(600.))
+            .w(width)
             .h_full()
             .border_l_1()
             .border_color(cx.theme().border)
             .bg(cx.theme().background)
             .child(self.render_header(file, cx))
-            .child(self.render_content(file, cx))
+            .child(self.render_actions(cx))
+            .when_some(self.conflict.as_ref(), |this, conflict| {
+                this.child(self.render_conflict(conflict, cx))
+            })
+            .map(
+                |this| match self.conflict.as_ref().and_then(|c| c.diff.as_ref()) {
+                    Some(lines) => this.child(self.render_diff(lines, cx)),
+                    None => this.child(self.render_content(window, cx)),
+                },
+            )
             .into_any_element()
     }
 }
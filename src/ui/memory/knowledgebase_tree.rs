@@ -0,0 +1,623 @@
+use super::columns::{
+    render_header, track_resize, Column, LayoutTable, SortDirection, TableLayout,
+};
+use crate::config::{AppConfig, TableLayoutConfig};
+use crate::knowledgebase::{FrontmatterFilter, KnowledgebaseEntry};
+use chrono::{DateTime, Utc};
+use gpui::*;
+use gpui_component::{h_flex, list::ListItem, v_flex, ActiveTheme, Icon, IconName, Sizable};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Columns of the knowledgebase tree, widths in pixels
+const COLUMNS: &[Column] = &[
+    Column {
+        id: "name",
+        label: "Name",
+        width: None,
+        sortable: true,
+    },
+    Column {
+        id: "status",
+        label: "Status",
+        width: Some(100.),
+        sortable: true,
+    },
+    Column {
+        id: "tags",
+        label: "Tags",
+        width: Some(160.),
+        sortable: false,
+    },
+    Column {
+        id: "persona",
+        label: "Persona",
+        width: Some(120.),
+        sortable: true,
+    },
+    Column {
+        id: "summary",
+        label: "Summary",
+        width: Some(240.),
+        sortable: false,
+    },
+    Column {
+        id: "modified",
+        label: "Modified",
+        width: Some(160.),
+        sortable: true,
+    },
+];
+
+/// Indentation per folder level
+const INDENT: f32 = 16.;
+
+/// Files of a folder and its subfolders by name
+#[derive(Debug, Default)]
+struct FolderNode {
+    folders: BTreeMap<String, FolderNode>,
+    files: Vec<KnowledgebaseEntry>,
+}
+
+impl FolderNode {
+    fn build(entries: &[KnowledgebaseEntry]) -> Self {
+        let mut root = Self::default();
+        for entry in entries {
+            let mut node = &mut root;
+            for component in entry.folder().components() {
+                node = node
+                    .folders
+                    .entry(component.as_os_str().to_string_lossy().to_string())
+                    .or_default();
+            }
+            node.files.push(entry.clone());
+        }
+        root
+    }
+
+    /// Most recent change to any file in the folder or below
+    fn modified_at(&self) -> Option<DateTime<Utc>> {
+        self.files
+            .iter()
+            .map(|file| file.modified_at)
+            .chain(
+                self.folders
+                    .values()
+                    .filter_map(|folder| folder.modified_at()),
+            )
+            .max()
+    }
+}
+
+/// A visible row of the tree
+#[derive(Debug, Clone)]
+enum TreeRow {
+    Folder {
+        /// Relative to the knowledgebase
+        path: PathBuf,
+        name: String,
+        modified_at: Option<DateTime<Utc>>,
+        depth: usize,
+        expanded: bool,
+    },
+    File {
+        entry: KnowledgebaseEntry,
+        depth: usize,
+    },
+}
+
+/// Flatten the folder tree into rows, folders before files, each sorted within
+/// its own folder
+fn tree_rows(
+    entries: &[KnowledgebaseEntry],
+    collapsed: &HashSet<PathBuf>,
+    sort: Option<(&'static str, SortDirection)>,
+) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    push_rows(
+        &FolderNode::build(entries),
+        Path::new(""),
+        0,
+        collapsed,
+        sort,
+        &mut rows,
+    );
+    rows
+}
+
+fn push_rows(
+    node: &FolderNode,
+    path: &Path,
+    depth: usize,
+    collapsed: &HashSet<PathBuf>,
+    sort: Option<(&'static str, SortDirection)>,
+    rows: &mut Vec<TreeRow>,
+) {
+    let mut folders: Vec<(&String, &FolderNode)> = node.folders.iter().collect();
+    // Without a sort column folders stay in name order
+    folders.sort_by(|(a_name, a), (b_name, b)| {
+        compare(sort, a_name, b_name, a.modified_at(), b.modified_at())
+    });
+
+    for (name, folder) in folders {
+        let folder_path = path.join(name);
+        let expanded = !collapsed.contains(&folder_path);
+        rows.push(TreeRow::Folder {
+            path: folder_path.clone(),
+            name: name.clone(),
+            modified_at: folder.modified_at(),
+            depth,
+            expanded,
+        });
+        if expanded {
+            push_rows(folder, &folder_path, depth + 1, collapsed, sort, rows);
+        }
+    }
+
+    let mut files = node.files.clone();
+    if sort.is_some() {
+        files.sort_by(|a, b| compare_files(sort, a, b));
+    }
+    rows.extend(
+        files
+            .into_iter()
+            .map(|entry| TreeRow::File { entry, depth }),
+    );
+}
+
+fn compare(
+    sort: Option<(&'static str, SortDirection)>,
+    a_name: &str,
+    b_name: &str,
+    a_modified: Option<DateTime<Utc>>,
+    b_modified: Option<DateTime<Utc>>,
+) -> Ordering {
+    let by_name = || a_name.to_lowercase().cmp(&b_name.to_lowercase());
+    let Some((column, direction)) = sort else {
+        return by_name();
+    };
+    let ordering = match column {
+        "modified" => a_modified.cmp(&b_modified),
+        _ => by_name(),
+    };
+    match direction {
+        SortDirection::Ascending => ordering,
+        SortDirection::Descending => ordering.reverse(),
+    }
+}
+
+/// Like `compare`, with frontmatter columns as well, files without a value last
+fn compare_files(
+    sort: Option<(&'static str, SortDirection)>,
+    a: &KnowledgebaseEntry,
+    b: &KnowledgebaseEntry,
+) -> Ordering {
+    let field = |entry: &KnowledgebaseEntry| match sort {
+        Some(("status", _)) => entry.frontmatter.status.clone(),
+        Some(("persona", _)) => entry.frontmatter.persona.clone(),
+        _ => None,
+    };
+    let (a_field, b_field) = (field(a), field(b));
+    let by_field = match (&a_field, &b_field, sort) {
+        (Some(a_field), Some(b_field), Some((_, direction))) => {
+            let ordering = a_field.to_lowercase().cmp(&b_field.to_lowercase());
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        }
+        (Some(_), None, _) => Ordering::Less,
+        (None, Some(_), _) => Ordering::Greater,
+        _ => Ordering::Equal,
+    };
+
+    by_field.then_with(|| {
+        compare(
+            sort,
+            &a.name,
+            &b.name,
+            Some(a.modified_at),
+            Some(b.modified_at),
+        )
+    })
+}
+
+/// Knowledgebase files laid out as a collapsible folder tree
+pub struct KnowledgebaseTree {
+    entries: Vec<KnowledgebaseEntry>,
+    rows: Vec<TreeRow>,
+    /// Folders the user collapsed, relative to the knowledgebase
+    collapsed: HashSet<PathBuf>,
+    /// Only files matching this are shown, along with the folders holding them
+    filter: FrontmatterFilter,
+    selected_path: Option<PathBuf>,
+    layout: TableLayout,
+    on_select: Box<dyn Fn(&KnowledgebaseEntry, &mut Window, &mut App) + 'static>,
+}
+
+impl KnowledgebaseTree {
+    pub fn new<F>(
+        entries: Vec<KnowledgebaseEntry>,
+        layout_config: &TableLayoutConfig,
+        on_select: F,
+    ) -> Self
+    where
+        F: Fn(&KnowledgebaseEntry, &mut Window, &mut App) + 'static,
+    {
+        let mut tree = Self {
+            entries,
+            rows: vec![],
+            collapsed: HashSet::new(),
+            filter: FrontmatterFilter::default(),
+            selected_path: None,
+            layout: TableLayout::new(COLUMNS, layout_config),
+            on_select: Box::new(on_select),
+        };
+        tree.rebuild_rows();
+        tree
+    }
+
+    /// Show another knowledgebase, with every folder expanded
+    pub fn set_entries(&mut self, entries: Vec<KnowledgebaseEntry>) {
+        self.entries = entries;
+        self.selected_path = None;
+        self.collapsed.clear();
+        self.rebuild_rows();
+    }
+
+    /// Take the entries again after files changed on disk, keeping the folders the
+    /// user collapsed and the selection unless that file is gone
+    pub fn refresh_entries(&mut self, entries: Vec<KnowledgebaseEntry>) {
+        self.entries = entries;
+        if let Some(path) = &self.selected_path {
+            if !self.entries.iter().any(|e| &e.file_path == path) {
+                self.selected_path = None;
+            }
+        }
+        self.rebuild_rows();
+    }
+
+    pub fn set_filter(&mut self, filter: FrontmatterFilter) {
+        self.filter = filter;
+        self.rebuild_rows();
+    }
+
+    pub fn set_selected(&mut self, path: Option<PathBuf>) {
+        self.selected_path = path;
+    }
+
+    /// Select the entry for a file and expand the folders above it, returning the
+    /// entry if it is in the tree
+    pub fn select_path(&mut self, path: &Path) -> Option<KnowledgebaseEntry> {
+        let entry = self.entries.iter().find(|e| e.file_path == path).cloned();
+        self.selected_path = entry.as_ref().map(|e| e.file_path.clone());
+        if let Some(entry) = &entry {
+            self.collapsed
+                .retain(|folder| !entry.folder().starts_with(folder));
+            self.rebuild_rows();
+        }
+        entry
+    }
+
+    fn toggle_folder(&mut self, path: PathBuf) {
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+        self.rebuild_rows();
+    }
+
+    fn rebuild_rows(&mut self) {
+        let entries: Vec<KnowledgebaseEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+            .cloned()
+            .collect();
+        self.rows = tree_rows(&entries, &self.collapsed, self.layout.sort());
+    }
+
+    fn render_row(&self, index: usize, row: &TreeRow, cx: &mut Context<Self>) -> impl IntoElement {
+        let entity = cx.entity().clone();
+        let (depth, icon, label, modified_at) = match row {
+            TreeRow::Folder {
+                name,
+                modified_at,
+                depth,
+                expanded,
+                ..
+            } => (
+                *depth,
+                if *expanded {
+                    IconName::ChevronDown
+                } else {
+                    IconName::ChevronRight
+                },
+                name.clone(),
+                *modified_at,
+            ),
+            TreeRow::File { entry, depth } => (
+                *depth,
+                IconName::File,
+                entry.name.clone(),
+                Some(entry.modified_at),
+            ),
+        };
+        let is_selected = match row {
+            TreeRow::File { entry, .. } => self.selected_path.as_ref() == Some(&entry.file_path),
+            TreeRow::Folder { .. } => false,
+        };
+        let modified = modified_at
+            .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let frontmatter = match row {
+            TreeRow::File { entry, .. } => entry.frontmatter.clone(),
+            TreeRow::Folder { .. } => Default::default(),
+        };
+        let clicked = row.clone();
+
+        ListItem::new(("kb-row", index))
+            .px_3()
+            .py_1()
+            .selected(is_selected)
+            .on_click(move |_, window, cx| {
+                entity.update(cx, |this, cx| {
+                    match &clicked {
+                        TreeRow::Folder { path, .. } => this.toggle_folder(path.clone()),
+                        TreeRow::File { entry, .. } => {
+                            this.selected_path = Some(entry.file_path.clone());
+                            (this.on_select)(entry, window, cx);
+                        }
+                    }
+                    cx.notify();
+                });
+            })
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_0()
+                    .child(
+                        self.layout.cell("name").child(
+                            h_flex()
+                                .pl(px(depth as f32 * INDENT))
+                                .gap_1()
+                                .items_center()
+                                .child(
+                                    Icon::new(icon)
+                                        .xsmall()
+                                        .text_color(cx.theme().muted_foreground),
+                                )
+                                .child(div().truncate().child(label)),
+                        ),
+                    )
+                    .child(
+                        self.layout
+                            .cell("status")
+                            .child(frontmatter.status.unwrap_or_default()),
+                    )
+                    .child(
+                        self.layout
+                            .cell("tags")
+                            .text_color(cx.theme().muted_foreground)
+                            .child(div().truncate().child(frontmatter.tags.join(", "))),
+                    )
+                    .child(
+                        self.layout
+                            .cell("persona")
+                            .child(frontmatter.persona.unwrap_or_default()),
+                    )
+                    .child(
+                        self.layout
+                            .cell("summary")
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                div()
+                                    .truncate()
+                                    .child(frontmatter.summary.unwrap_or_default()),
+                            ),
+                    )
+                    .child(
+                        self.layout
+                            .cell("modified")
+                            .text_color(cx.theme().muted_foreground)
+                            .child(modified),
+                    ),
+            )
+    }
+}
+
+impl Render for KnowledgebaseTree {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows: Vec<_> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| self.render_row(i, row, cx))
+            .collect();
+
+        let header = render_header(&self.layout, None, cx);
+
+        track_resize(v_flex().id("kb-tree"), cx)
+            .w_full()
+            .flex_1()
+            .overflow_hidden()
+            .child(header)
+            .child(
+                v_flex()
+                    .id("kb-tree-rows")
+                    .w_full()
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .children(rows),
+            )
+    }
+}
+
+impl LayoutTable for KnowledgebaseTree {
+    fn layout(&mut self) -> &mut TableLayout {
+        &mut self.layout
+    }
+
+    fn layout_changed(&mut self, cx: &mut Context<Self>) {
+        self.rebuild_rows();
+
+        let layout = self.layout.to_config();
+        if let Err(e) = AppConfig::update(|app_config| app_config.tables.knowledgebase = layout) {
+            eprintln!("Failed to save config: {}", e);
+        }
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledgebase::Frontmatter;
+    use chrono::TimeZone;
+
+    fn entry(relative_path: &str, name: &str, day: u32) -> KnowledgebaseEntry {
+        KnowledgebaseEntry {
+            file_path: Path::new("/kb").join(relative_path),
+            relative_path: PathBuf::from(relative_path),
+            name: name.to_string(),
+            modified_at: Utc.with_ymd_and_hms(2026, 2, day, 9, 0, 0).unwrap(),
+            frontmatter: Frontmatter::default(),
+        }
+    }
+
+    fn with_status(mut entry: KnowledgebaseEntry, status: &str) -> KnowledgebaseEntry {
+        entry.frontmatter.status = Some(status.to_string());
+        entry
+    }
+
+    fn labels(rows: &[TreeRow]) -> Vec<String> {
+        rows.iter()
+            .map(|row| match row {
+                TreeRow::Folder { name, depth, .. } => format!("{}{}/", "  ".repeat(*depth), name),
+                TreeRow::File { entry, depth } => format!("{}{}", "  ".repeat(*depth), entry.name),
+            })
+            .collect()
+    }
+
+    fn entries() -> Vec<KnowledgebaseEntry> {
+        vec![
+            entry("index.md", "Index", 1),
+            entry("projects/berry/api.md", "API", 3),
+            entry("projects/berry/schema.md", "Schema", 5),
+            entry("projects/roadmap.md", "Roadmap", 2),
+            entry("archive/old.md", "Old", 4),
+        ]
+    }
+
+    #[test]
+    fn test_folders_come_before_files() {
+        let rows = tree_rows(
+            &entries(),
+            &HashSet::new(),
+            Some(("name", SortDirection::Ascending)),
+        );
+
+        assert_eq!(
+            labels(&rows),
+            vec![
+                "archive/",
+                "  Old",
+                "projects/",
+                "  berry/",
+                "    API",
+                "    Schema",
+                "  Roadmap",
+                "Index",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_modified_within_each_folder() {
+        let rows = tree_rows(
+            &entries(),
+            &HashSet::new(),
+            Some(("modified", SortDirection::Descending)),
+        );
+
+        // projects holds the newest file so it comes before archive
+        assert_eq!(
+            labels(&rows),
+            vec![
+                "projects/",
+                "  berry/",
+                "    Schema",
+                "    API",
+                "  Roadmap",
+                "archive/",
+                "  Old",
+                "Index",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_refresh_keeps_collapsed_folders_and_selection() {
+        let mut tree =
+            KnowledgebaseTree::new(entries(), &TableLayoutConfig::default(), |_, _, _| {});
+        tree.toggle_folder(PathBuf::from("projects/berry"));
+        tree.set_selected(Some(PathBuf::from("/kb/index.md")));
+
+        let mut refreshed = entries();
+        refreshed.push(entry("projects/berry/notes.md", "Notes", 6));
+        tree.refresh_entries(refreshed.clone());
+        assert!(tree.collapsed.contains(Path::new("projects/berry")));
+        assert!(!labels(&tree.rows)
+            .iter()
+            .any(|label| label.trim() == "Notes"));
+        assert_eq!(tree.selected_path, Some(PathBuf::from("/kb/index.md")));
+
+        refreshed.retain(|e| e.name != "Index");
+        tree.refresh_entries(refreshed);
+        assert_eq!(tree.selected_path, None);
+
+        tree.set_entries(entries());
+        assert!(tree.collapsed.is_empty());
+    }
+
+    #[test]
+    fn test_collapsed_folder_hides_its_contents() {
+        let collapsed = HashSet::from([PathBuf::from("projects/berry")]);
+        let rows = tree_rows(&entries(), &collapsed, None);
+
+        assert_eq!(
+            labels(&rows),
+            vec![
+                "archive/",
+                "  Old",
+                "projects/",
+                "  berry/",
+                "  Roadmap",
+                "Index"
+            ]
+        );
+        assert!(matches!(
+            &rows[3],
+            TreeRow::Folder {
+                expanded: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_sort_by_status_puts_files_without_one_last() {
+        let entries = vec![
+            with_status(entry("review.md", "Review", 1), "final"),
+            entry("index.md", "Index", 2),
+            with_status(entry("plan.md", "Plan", 3), "draft"),
+        ];
+        let rows = tree_rows(
+            &entries,
+            &HashSet::new(),
+            Some(("status", SortDirection::Ascending)),
+        );
+
+        assert_eq!(labels(&rows), vec!["Plan", "Review", "Index"]);
+    }
+}
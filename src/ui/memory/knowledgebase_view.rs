@@ -1,13 +1,27 @@
-use crate::knowledgebase::{self, KnowledgebaseEntry};
+use crate::config::AppConfig;
+use crate::knowledgebase::{
+    self, FrontmatterFilter, KnowledgebaseEntry, KnowledgebaseIndex, SearchHit, Snapshot,
+};
 use crate::persona::Persona;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::list::ListItem;
 use gpui_component::tab::{Tab, TabBar};
-use gpui_component::{h_flex, v_flex, ActiveTheme};
-use std::path::PathBuf;
+use gpui_component::{h_flex, v_flex, ActiveTheme, IconName, Selectable, Sizable};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use super::knowledgebase_slideout::KnowledgebaseSlideout;
-use super::knowledgebase_table::KnowledgebaseTable;
+use super::knowledgebase_slideout::{KnowledgebaseSlideout, KnowledgebaseSlideoutEvent};
+use super::knowledgebase_tree::KnowledgebaseTree;
+
+/// Most search results shown at once
+const SEARCH_LIMIT: usize = 50;
+
+/// How often the selected knowledgebase is checked for files changed outside the app
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Clone)]
 pub struct PersonaTab {
@@ -15,18 +29,47 @@ pub struct PersonaTab {
     pub kb_path: PathBuf,
 }
 
+/// Which knowledgebases a search covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchScope {
+    /// The knowledgebase of the selected tab
+    Persona,
+    All,
+}
+
+/// Inputs for a document being created in the selected knowledgebase
+struct NewDocumentForm {
+    title_input: Entity<InputState>,
+    /// Folder relative to the knowledgebase, empty for the top level
+    folder_input: Entity<InputState>,
+}
+
 pub struct KnowledgebaseView {
     personas: Vec<PersonaTab>,
     selected_index: usize,
     entries: Vec<KnowledgebaseEntry>,
-    table: Entity<KnowledgebaseTable>,
+    /// Built on the first search and refreshed as searches start
+    index: KnowledgebaseIndex,
+    search_input: Entity<InputState>,
+    search_scope: SearchScope,
+    /// Query the results are for, empty when the tree is shown
+    query: String,
+    results: Vec<SearchHit>,
+    /// Status and tags from frontmatter narrowing both the tree and search results
+    filter: FrontmatterFilter,
+    tree: Entity<KnowledgebaseTree>,
     slideout: Entity<KnowledgebaseSlideout>,
     slideout_open: bool,
     selected_entry: Option<KnowledgebaseEntry>,
+    new_document: Option<NewDocumentForm>,
+    error: Option<String>,
+    /// Files of the selected knowledgebase as of the last reload
+    snapshot: Snapshot,
+    _watch_task: Task<()>,
 }
 
 impl KnowledgebaseView {
-    pub fn new(personas: Vec<Persona>, cx: &mut Context<Self>) -> Self {
+    pub fn new(personas: Vec<Persona>, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let entity = cx.entity().clone();
 
         // Filter personas that have knowledgebases
@@ -40,15 +83,22 @@ impl KnowledgebaseView {
             })
             .collect();
 
-        // Create table with selection callback
-        let entity_for_table = entity.clone();
-        let table = cx.new(|_cx| {
-            KnowledgebaseTable::new(vec![], move |_index, entry, window, cx| {
-                entity_for_table.update(cx, |this, cx| {
+        // Create tree with selection callback
+        let entity_for_tree = entity.clone();
+        let layout_config = AppConfig::load().tables.knowledgebase;
+        let tree = cx.new(|_cx| {
+            KnowledgebaseTree::new(vec![], &layout_config, move |entry, window, cx| {
+                entity_for_tree.update(cx, |this, cx| {
+                    if !this.save_open_edits(cx) {
+                        return;
+                    }
                     this.selected_entry = Some(entry.clone());
                     this.slideout_open = true;
 
-                    if let Ok(file) = knowledgebase::load_file(&entry.file_path) {
+                    let Some(kb_path) = this.selected_kb_path() else {
+                        return;
+                    };
+                    if let Ok(file) = knowledgebase::load_file(&kb_path, &entry.file_path) {
                         this.slideout.update(cx, |slideout, cx| {
                             slideout.set_file(Some(file), window, cx);
                             cx.notify();
@@ -61,28 +111,91 @@ impl KnowledgebaseView {
 
         // Create slideout with close callback
         let entity_for_slideout = entity.clone();
+        let knowledgebases = persona_tabs.clone();
         let slideout = cx.new(|_cx| {
-            KnowledgebaseSlideout::new(move |_window, cx| {
+            KnowledgebaseSlideout::new(knowledgebases, move |_window, cx| {
                 entity_for_slideout.update(cx, |this, cx| {
-                    this.slideout_open = false;
-                    this.selected_entry = None;
-                    this.table.update(cx, |table, cx| {
-                        table.set_selected(None);
-                        cx.notify();
-                    });
-                    cx.notify();
+                    this.close_slideout(cx);
                 });
             })
         });
+        cx.subscribe_in(
+            &slideout,
+            window,
+            |this, _slideout, event: &KnowledgebaseSlideoutEvent, window, cx| {
+                this.handle_file_action(event, window, cx);
+            },
+        )
+        .detach();
+
+        let search_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Search knowledgebase..."));
+        cx.subscribe(
+            &search_input,
+            |this, _input, event: &InputEvent, cx| match event {
+                InputEvent::Change => this.search(false, cx),
+                InputEvent::PressEnter { .. } => this.search(true, cx),
+                _ => {}
+            },
+        )
+        .detach();
+
+        // Agents write into knowledgebases during sessions, so keep checking for
+        // their changes. Scans and rereading the entries run in the background and
+        // are dropped if the tab changed in the meantime.
+        let watch_task = cx.spawn_in(window, async move |this, cx| loop {
+            cx.background_executor().timer(WATCH_INTERVAL).await;
+            let Ok((kb_path, previous)) = this.read_with(cx, |this, _cx| {
+                (this.selected_kb_path(), this.snapshot.clone())
+            }) else {
+                break;
+            };
+            let Some(kb_path) = kb_path else {
+                continue;
+            };
+            let scanned = kb_path.clone();
+            let (snapshot, entries) = cx
+                .background_executor()
+                .spawn(async move {
+                    let snapshot = Snapshot::scan(&scanned);
+                    if snapshot == previous {
+                        return (snapshot, None);
+                    }
+                    let entries = knowledgebase::load_entries(&scanned);
+                    (snapshot, Some(entries))
+                })
+                .await;
+            let Some(entries) = entries else {
+                continue;
+            };
+            if this
+                .update_in(cx, |this, window, cx| {
+                    this.apply_snapshot(&kb_path, snapshot, entries, window, cx)
+                })
+                .is_err()
+            {
+                break;
+            }
+        });
 
         let mut view = Self {
             personas: persona_tabs,
             selected_index: 0,
             entries: vec![],
-            table,
+            index: KnowledgebaseIndex::new(),
+            search_input,
+            search_scope: SearchScope::Persona,
+            query: String::new(),
+            results: vec![],
+            filter: FrontmatterFilter::default(),
+            tree,
             slideout,
             slideout_open: false,
             selected_entry: None,
+            new_document: None,
+            error: None,
+            snapshot: Snapshot::default(),
+            _watch_task: watch_task,
         };
 
         // Load initial entries if there are personas
@@ -91,20 +204,273 @@ impl KnowledgebaseView {
         view
     }
 
+    fn selected_kb_path(&self) -> Option<PathBuf> {
+        self.personas
+            .get(self.selected_index)
+            .map(|persona| persona.kb_path.clone())
+    }
+
     fn load_entries_for_selected(&mut self, cx: &mut Context<Self>) {
+        let selected_path = self.selected_entry.as_ref().map(|e| e.file_path.clone());
+
         if let Some(persona) = self.personas.get(self.selected_index) {
             let entries = knowledgebase::load_entries(&persona.kb_path);
+            self.snapshot = Snapshot::scan(&persona.kb_path);
             self.entries = entries.clone();
-            self.table.update(cx, |table, cx| {
-                table.set_entries(entries);
+            self.tree.update(cx, |tree, cx| {
+                tree.set_entries(entries);
+                tree.set_selected(selected_path);
                 cx.notify();
             });
         } else {
             self.entries = vec![];
-            self.table.update(cx, |table, cx| {
-                table.set_entries(vec![]);
+            self.snapshot = Snapshot::default();
+            self.tree.update(cx, |tree, cx| {
+                tree.set_entries(vec![]);
+                cx.notify();
+            });
+        }
+    }
+
+    /// Reread the selected knowledgebase after its files changed, leaving the
+    /// tree's collapsed folders and selection as they are
+    fn refresh_entries_for_selected(&mut self, cx: &mut Context<Self>) {
+        let Some(persona) = self.personas.get(self.selected_index) else {
+            self.load_entries_for_selected(cx);
+            return;
+        };
+
+        let entries = knowledgebase::load_entries(&persona.kb_path);
+        let snapshot = Snapshot::scan(&persona.kb_path);
+        self.show_refreshed_entries(entries, snapshot, cx);
+    }
+
+    fn show_refreshed_entries(
+        &mut self,
+        entries: Vec<KnowledgebaseEntry>,
+        snapshot: Snapshot,
+        cx: &mut Context<Self>,
+    ) {
+        self.snapshot = snapshot;
+        self.entries = entries.clone();
+        self.tree.update(cx, |tree, cx| {
+            tree.refresh_entries(entries);
+            cx.notify();
+        });
+    }
+
+    /// Show the entries read after files in the selected knowledgebase changed on disk
+    fn apply_snapshot(
+        &mut self,
+        kb_path: &Path,
+        snapshot: Snapshot,
+        entries: Vec<KnowledgebaseEntry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_kb_path().as_deref() != Some(kb_path) || snapshot == self.snapshot {
+            return;
+        }
+
+        let open_file_changed = self
+            .selected_entry
+            .as_ref()
+            .is_some_and(|entry| self.snapshot.file_changed(&snapshot, &entry.file_path));
+
+        self.show_refreshed_entries(entries, snapshot, cx);
+        if !self.query.is_empty() {
+            self.search(true, cx);
+        }
+        if open_file_changed {
+            self.slideout.update(cx, |slideout, cx| {
+                slideout.check_disk(window, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Save the open file's unsaved edits before another file replaces it
+    ///
+    /// If they couldn't be saved the slideout stays on the file, showing why, and
+    /// false is returned.
+    fn save_open_edits(&mut self, cx: &mut Context<Self>) -> bool {
+        if self
+            .slideout
+            .update(cx, |slideout, cx| slideout.save_edits(cx))
+        {
+            return true;
+        }
+
+        self.slideout_open = true;
+        // Deferred as this runs inside the tree's own click handler too
+        let selected_path = self.selected_entry.as_ref().map(|e| e.file_path.clone());
+        let tree = self.tree.clone();
+        cx.defer(move |cx| {
+            tree.update(cx, |tree, cx| {
+                tree.set_selected(selected_path);
                 cx.notify();
             });
+        });
+        cx.notify();
+        false
+    }
+
+    /// Switch to the persona owning a knowledgebase and open one of its files
+    pub fn open_file(
+        &mut self,
+        kb_path: &Path,
+        file_path: &Path,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.save_open_edits(cx) {
+            return;
+        }
+        match self.personas.iter().position(|p| p.kb_path == kb_path) {
+            Some(index) if index != self.selected_index => {
+                self.selected_index = index;
+                self.load_entries_for_selected(cx);
+            }
+            _ => self.refresh_entries_for_selected(cx),
+        }
+
+        let entry = self.tree.update(cx, |tree, cx| {
+            cx.notify();
+            tree.select_path(file_path)
+        });
+
+        match knowledgebase::load_file(kb_path, file_path) {
+            Ok(file) => {
+                self.selected_entry = entry;
+                self.slideout_open = true;
+                self.slideout.update(cx, |slideout, cx| {
+                    slideout.set_file(Some(file), window, cx);
+                    cx.notify();
+                });
+            }
+            Err(_) => {
+                self.selected_entry = None;
+                self.slideout_open = false;
+            }
+        }
+        cx.notify();
+    }
+
+    fn close_slideout(&mut self, cx: &mut Context<Self>) {
+        self.slideout_open = false;
+        self.selected_entry = None;
+        self.tree.update(cx, |tree, cx| {
+            tree.set_selected(None);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// Apply a rename, duplicate, move or delete from the slideout to the open file
+    fn handle_file_action(
+        &mut self,
+        event: &KnowledgebaseSlideoutEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(kb_path) = self.selected_kb_path() else {
+            return;
+        };
+
+        // The knowledgebase and file to open afterwards, none once the file is gone
+        let result = match event {
+            KnowledgebaseSlideoutEvent::Rename { path, new_name } => {
+                knowledgebase::rename_file(&kb_path, path, new_name)
+                    .map(|path| Some((kb_path.clone(), path)))
+            }
+            KnowledgebaseSlideoutEvent::Duplicate { path } => {
+                knowledgebase::duplicate_file(&kb_path, path)
+                    .map(|path| Some((kb_path.clone(), path)))
+            }
+            KnowledgebaseSlideoutEvent::Move {
+                path,
+                kb_path: to_kb,
+            } => knowledgebase::move_file(&kb_path, path, to_kb)
+                .map(|path| Some((to_kb.clone(), path))),
+            KnowledgebaseSlideoutEvent::Delete { path } => {
+                knowledgebase::trash_file(&kb_path, path).map(|_| None)
+            }
+        };
+
+        match result {
+            Ok(Some((kb_path, path))) => self.open_file(&kb_path, &path, window, cx),
+            Ok(None) => {
+                self.close_slideout(cx);
+                self.refresh_entries_for_selected(cx);
+            }
+            Err(e) => {
+                self.slideout.update(cx, |slideout, cx| {
+                    slideout.set_error(Some(e.to_string()));
+                    cx.notify();
+                });
+                return;
+            }
+        }
+
+        if !self.query.is_empty() {
+            self.search(true, cx);
+        }
+    }
+
+    fn start_new_document(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // New documents go next to the open one unless another folder is given
+        let folder = self
+            .selected_entry
+            .as_ref()
+            .map(|entry| entry.folder().display().to_string())
+            .unwrap_or_default();
+
+        let title_input = cx.new(|cx| InputState::new(window, cx).placeholder("Title"));
+        let folder_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Folder (optional)")
+                .default_value(folder)
+        });
+        cx.subscribe_in(
+            &title_input,
+            window,
+            |this, _input, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.create_document(window, cx);
+                }
+            },
+        )
+        .detach();
+
+        self.new_document = Some(NewDocumentForm {
+            title_input,
+            folder_input,
+        });
+        self.error = None;
+        cx.notify();
+    }
+
+    fn create_document(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(kb_path), Some(form)) = (self.selected_kb_path(), &self.new_document) else {
+            return;
+        };
+
+        let title = form.title_input.read(cx).text().to_string();
+        let folder = form.folder_input.read(cx).text().trim().to_string();
+
+        match knowledgebase::new_document(&kb_path, Path::new(&folder), &title) {
+            Ok(path) => {
+                self.new_document = None;
+                self.error = None;
+                self.open_file(&kb_path, &path, window, cx);
+                if !self.query.is_empty() {
+                    self.search(true, cx);
+                }
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                cx.notify();
+            }
         }
     }
 
@@ -114,9 +480,91 @@ impl KnowledgebaseView {
             // Close slideout when switching tabs
             self.slideout_open = false;
             self.selected_entry = None;
+            // Tags and statuses differ between knowledgebases
+            self.set_filter(FrontmatterFilter::default(), cx);
             self.load_entries_for_selected(cx);
+            if self.search_scope == SearchScope::Persona {
+                self.search(true, cx);
+            }
+            cx.notify();
+        }
+    }
+
+    fn scope_path(&self) -> Option<PathBuf> {
+        match self.search_scope {
+            SearchScope::Persona => self.selected_kb_path(),
+            SearchScope::All => None,
+        }
+    }
+
+    /// Re-index files changed since the last search in the knowledgebases searched
+    fn refresh_index(&mut self) {
+        let kb_paths: Vec<PathBuf> = match self.scope_path() {
+            Some(kb_path) => vec![kb_path],
+            None => self.personas.iter().map(|p| p.kb_path.clone()).collect(),
+        };
+        for kb_path in kb_paths {
+            self.index.update(&kb_path);
+        }
+    }
+
+    /// Run the query in the search box, refreshing the index first when asked to
+    /// or when a new search starts
+    fn search(&mut self, refresh: bool, cx: &mut Context<Self>) {
+        let query = self.search_input.read(cx).text().trim().to_string();
+        if query.is_empty() {
+            self.query.clear();
+            self.results.clear();
             cx.notify();
+            return;
+        }
+
+        if refresh || self.query.is_empty() {
+            self.refresh_index();
         }
+        self.results = self
+            .index
+            .search(&query, self.scope_path().as_deref(), SEARCH_LIMIT);
+        self.results.retain(|hit| self.filter.matches(&hit.entry));
+        self.query = query;
+        cx.notify();
+    }
+
+    fn set_search_scope(&mut self, scope: SearchScope, cx: &mut Context<Self>) {
+        if scope != self.search_scope {
+            self.search_scope = scope;
+            self.search(true, cx);
+        }
+    }
+
+    fn set_filter(&mut self, filter: FrontmatterFilter, cx: &mut Context<Self>) {
+        self.filter = filter.clone();
+        self.tree.update(cx, |tree, cx| {
+            tree.set_filter(filter);
+            cx.notify();
+        });
+        if !self.query.is_empty() {
+            self.search(false, cx);
+        }
+        cx.notify();
+    }
+
+    fn set_status_filter(&mut self, status: Option<String>, cx: &mut Context<Self>) {
+        let filter = FrontmatterFilter {
+            status,
+            ..self.filter.clone()
+        };
+        self.set_filter(filter, cx);
+    }
+
+    fn toggle_tag_filter(&mut self, tag: &str, cx: &mut Context<Self>) {
+        let mut filter = self.filter.clone();
+        if let Some(index) = filter.tags.iter().position(|t| t == tag) {
+            filter.tags.remove(index);
+        } else {
+            filter.tags.push(tag.to_string());
+        }
+        self.set_filter(filter, cx);
     }
 
     fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
@@ -138,6 +586,290 @@ impl KnowledgebaseView {
             .child(tab_bar)
     }
 
+    fn render_search_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_4()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                Input::new(&self.search_input)
+                    .w_full()
+                    .cleanable(true)
+                    .appearance(false),
+            )
+            .child(
+                Button::new("kb-scope-persona")
+                    .label("This Persona")
+                    .ghost()
+                    .small()
+                    .selected(self.search_scope == SearchScope::Persona)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.set_search_scope(SearchScope::Persona, cx);
+                    })),
+            )
+            .child(
+                Button::new("kb-scope-all")
+                    .label("All Personas")
+                    .ghost()
+                    .small()
+                    .selected(self.search_scope == SearchScope::All)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.set_search_scope(SearchScope::All, cx);
+                    })),
+            )
+            .child(
+                Button::new("kb-new-document")
+                    .icon(IconName::Plus)
+                    .label("New")
+                    .small()
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.start_new_document(window, cx);
+                    })),
+            )
+    }
+
+    fn render_new_document(
+        &self,
+        form: &NewDocumentForm,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .px_4()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(div().flex_1().child(Input::new(&form.title_input).small()))
+                    .child(
+                        div()
+                            .w(px(200.))
+                            .child(Input::new(&form.folder_input).small()),
+                    )
+                    .child(
+                        Button::new("kb-create-document")
+                            .label("Create")
+                            .primary()
+                            .small()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.create_document(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("kb-cancel-document")
+                            .label("Cancel")
+                            .ghost()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.new_document = None;
+                                this.error = None;
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .w_full()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+    }
+
+    fn filter_chip(
+        id: impl Into<ElementId>,
+        label: impl Into<SharedString>,
+        selected: bool,
+    ) -> Button {
+        Button::new(id)
+            .label(label)
+            .xsmall()
+            .outline()
+            .selected(selected)
+    }
+
+    /// Status and tag chips built from the frontmatter of the selected knowledgebase
+    fn render_filters(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let mut statuses = BTreeSet::new();
+        let mut tags: BTreeSet<String> = self.filter.tags.iter().cloned().collect();
+        for entry in &self.entries {
+            statuses.extend(entry.frontmatter.status.clone());
+            tags.extend(entry.frontmatter.tags.iter().cloned());
+        }
+        statuses.extend(self.filter.status.clone());
+        if statuses.is_empty() && tags.is_empty() {
+            return None;
+        }
+        let has_statuses = !statuses.is_empty();
+
+        let mut status_chips =
+            vec![
+                Self::filter_chip("kb-status-all", "All", self.filter.status.is_none()).on_click(
+                    cx.listener(|this, _, _window, cx| {
+                        this.set_status_filter(None, cx);
+                    }),
+                ),
+            ];
+        for (index, status) in statuses.into_iter().enumerate() {
+            let selected = self.filter.status.as_ref() == Some(&status);
+            status_chips.push(
+                Self::filter_chip(("kb-status", index), status.clone(), selected).on_click(
+                    cx.listener(move |this, _, _window, cx| {
+                        this.set_status_filter(Some(status.clone()), cx);
+                    }),
+                ),
+            );
+        }
+
+        let tag_chips: Vec<Button> = tags
+            .into_iter()
+            .enumerate()
+            .map(|(index, tag)| {
+                let selected = self.filter.tags.contains(&tag);
+                Self::filter_chip(("kb-tag", index), tag.clone(), selected).on_click(cx.listener(
+                    move |this, _, _window, cx| {
+                        this.toggle_tag_filter(&tag, cx);
+                    },
+                ))
+            })
+            .collect();
+
+        let facet = |label: &'static str, chips: Vec<Button>| {
+            h_flex()
+                .w_full()
+                .gap_2()
+                .items_start()
+                .child(
+                    div()
+                        .w(px(60.))
+                        .pt_0p5()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(label),
+                )
+                .child(h_flex().flex_1().flex_wrap().gap_1().children(chips))
+        };
+
+        Some(
+            v_flex()
+                .w_full()
+                .px_4()
+                .py_2()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .when(has_statuses, |this| {
+                    this.child(facet("Status", status_chips))
+                })
+                .when(!tag_chips.is_empty(), |this| {
+                    this.child(facet("Tags", tag_chips))
+                }),
+        )
+    }
+
+    fn render_results(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.results.is_empty() {
+            return div()
+                .flex_1()
+                .size_full()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_color(cx.theme().muted_foreground)
+                .child(format!("No matches for \"{}\"", self.query))
+                .into_any_element();
+        }
+
+        let highlight = HighlightStyle {
+            font_weight: Some(FontWeight::BOLD),
+            background_color: Some(cx.theme().warning.opacity(0.3)),
+            ..Default::default()
+        };
+
+        let rows: Vec<_> = self
+            .results
+            .iter()
+            .enumerate()
+            .map(|(index, hit)| {
+                let persona = (self.search_scope == SearchScope::All)
+                    .then(|| self.personas.iter().find(|p| p.kb_path == hit.kb_path))
+                    .flatten()
+                    .map(|p| p.name.clone());
+                let is_selected = self
+                    .selected_entry
+                    .as_ref()
+                    .is_some_and(|e| e.file_path == hit.entry.file_path);
+                let snippet = StyledText::new(hit.snippet.text.clone()).with_highlights(
+                    hit.snippet
+                        .highlights
+                        .iter()
+                        .map(|range| (range.clone(), highlight)),
+                );
+                let opened = hit.clone();
+
+                ListItem::new(("kb-hit", index))
+                    .px_4()
+                    .py_2()
+                    .selected(is_selected)
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_file(&opened.kb_path, &opened.entry.file_path, window, cx);
+                    }))
+                    .child(
+                        v_flex()
+                            .w_full()
+                            .gap_1()
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .child(hit.entry.name.clone()),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(hit.entry.relative_path.display().to_string()),
+                                    )
+                                    .when_some(persona, |this, persona| {
+                                        this.child(
+                                            div()
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground)
+                                                .child(persona),
+                                        )
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(snippet),
+                            ),
+                    )
+            })
+            .collect();
+
+        v_flex()
+            .id("kb-search-results")
+            .w_full()
+            .flex_1()
+            .overflow_y_scroll()
+            .children(rows)
+            .into_any_element()
+    }
+
     fn render_content(&self, cx: &mut Context<Self>) -> impl IntoElement {
         if self.personas.is_empty() {
             div()
@@ -149,6 +881,8 @@ impl KnowledgebaseView {
                 .text_color(cx.theme().muted_foreground)
                 .child("No personas with knowledgebases found")
                 .into_any_element()
+        } else if !self.query.is_empty() {
+            self.render_results(cx).into_any_element()
         } else if self.entries.is_empty() {
             div()
                 .flex_1()
@@ -160,7 +894,7 @@ impl KnowledgebaseView {
                 .child("No knowledgebase files found")
                 .into_any_element()
         } else {
-            self.table.clone().into_any_element()
+            self.tree.clone().into_any_element()
         }
     }
 }
@@ -172,6 +906,11 @@ impl Render for KnowledgebaseView {
             .h_full()
             .when(!self.personas.is_empty(), |this| {
                 this.child(self.render_tabs(cx))
+                    .child(self.render_search_bar(cx))
+                    .children(self.render_filters(cx))
+            })
+            .when_some(self.new_document.as_ref(), |this, form| {
+                this.child(self.render_new_document(form, cx))
             })
             .child(self.render_content(cx));
 
@@ -1,24 +1,55 @@
+mod badge;
+mod bulk_actions;
+mod columns;
+mod composer;
+mod filter_bar;
+mod health;
 mod knowledgebase_slideout;
-mod knowledgebase_table;
+mod knowledgebase_tree;
 mod knowledgebase_view;
+mod related;
+mod sharing;
 mod slideout;
 mod table;
+mod timeline;
+mod transfer;
 
-use crate::memory::{BerryClient, BerryError, Memory, SearchRequest};
+use crate::config::{AppConfig, BerryConfig};
+use crate::knowledgebase;
+use crate::memory::transfer::{document_title, to_document, PROMOTED_TAG};
+use crate::memory::{
+    replay, BerryClient, BerryError, BerryStatus, Memory, MemoryCache, RememberRequest, SearchPage,
+    SearchRequest, SyncReport, UpdateMemoryRequest,
+};
 use crate::persona::Persona;
+use bulk_actions::{BulkActionBar, BulkActionEvent};
+use composer::{MemoryComposer, MemoryComposerEvent};
+use filter_bar::{MemoryFilterBar, MemoryFilterBarEvent};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
+    button::{Button, ButtonVariants},
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
     list::ListItem,
-    v_flex, ActiveTheme, Icon, IconName, Sizable,
+    select::{Select, SelectEvent, SelectState},
+    v_flex, ActiveTheme, Disableable, Icon, IconName, IndexPath, Selectable, Sizable,
 };
 use gpui_tokio_bridge::{JoinError, Tokio};
-use knowledgebase_view::KnowledgebaseView;
-use slideout::MemorySlideout;
-use table::MemoryTable;
+pub use health::BerryHealthMonitor;
+use knowledgebase_view::{KnowledgebaseView, PersonaTab};
+use slideout::{MemorySlideout, MemorySlideoutEvent};
+use std::path::Path;
+use table::{MemoryTable, MemoryTableEvent};
+use timeline::{render_timeline, TimelineGrouping};
+use transfer::{MemoryTransferEvent, MemoryTransferPanel};
+
+/// Actor identity used for Berry requests made from the UI
+const UI_ACTOR: &str = "persona-ui";
+
+/// Number of memories requested per page
+const PAGE_SIZE: u32 = 25;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MemoryCategory {
@@ -54,63 +85,362 @@ pub enum LoadingState<T> {
     Error(String),
 }
 
+/// How the loaded memories are laid out in the Berry category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BerryLayout {
+    Table,
+    Timeline,
+}
+
+/// How the view reacts to the result of a search request
+#[derive(Debug)]
+enum SearchOutcome {
+    Page(SearchPage),
+    /// Berry couldn't be reached, so cached results are shown instead
+    Unreachable,
+    /// Berry rejected the configured credentials
+    Unauthorized(String),
+    Failed(String),
+}
+
+impl SearchOutcome {
+    fn from_result(result: Result<Result<SearchPage, BerryError>, JoinError>) -> Self {
+        match result {
+            Ok(Ok(page)) => SearchOutcome::Page(page),
+            Ok(Err(e)) if e.is_unreachable() => SearchOutcome::Unreachable,
+            Ok(Err(e)) if e.is_auth_failure() => SearchOutcome::Unauthorized(e.to_string()),
+            Ok(Err(e)) => SearchOutcome::Failed(e.to_string()),
+            Err(e) => SearchOutcome::Failed(e.to_string()),
+        }
+    }
+}
+
+/// Memories a bulk operation succeeded on, and an error for each it failed on
+#[derive(Debug, Default)]
+struct BulkOutcome {
+    done: Vec<String>,
+    failed: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MemoryViewEvent {
+    /// Another Berry profile was made active from the profile switcher
+    ProfileSwitched(String),
+}
+
+impl EventEmitter<MemoryViewEvent> for MemoryView {}
+
 pub struct MemoryView {
     selected_category: MemoryCategory,
     client: BerryClient,
+    /// Name of the active Berry profile
+    profile: String,
+    profile_select: Entity<SelectState<Vec<String>>>,
+    health: Entity<BerryHealthMonitor>,
+    /// Whether the last health check failed, used to reload when Berry returns
+    berry_offline: bool,
+    /// Whether the last health check succeeded, so queued changes are synced as soon
+    /// as Berry is first seen online
+    berry_online: bool,
+    /// Memories fetched so far and changes made while offline
+    cache: MemoryCache,
+    /// Whether the table is showing cached results instead of live ones
+    showing_cached: bool,
+    syncing: bool,
+    /// Result of the last sync or bulk action, shown above the table
+    notice: Option<String>,
     memories: LoadingState<Vec<Memory>>,
+    /// Total number of memories matching the current search
+    total: usize,
+    loading_more: bool,
+    /// Why the last "Load More" failed, shown next to the button while the loaded rows stay
+    load_more_error: Option<String>,
+    /// Incremented for every new search so stale pages can be dropped
+    search_generation: usize,
     selected_memory: Option<Memory>,
     search_input: Option<Entity<InputState>>,
+    /// Actor that searches and edits are performed as
+    actor: String,
+    /// Actors offered in the actor select
+    actors: Vec<String>,
+    actor_select: Entity<SelectState<Vec<String>>>,
+    filter_bar: Entity<MemoryFilterBar>,
     table: Entity<MemoryTable>,
+    layout: BerryLayout,
+    timeline_grouping: TimelineGrouping,
+    bulk_bar: Entity<BulkActionBar>,
     slideout: Entity<MemorySlideout>,
     slideout_open: bool,
+    composer: Entity<MemoryComposer>,
+    composer_open: bool,
+    transfer: Entity<MemoryTransferPanel>,
+    transfer_open: bool,
     // Knowledgebase view
     kb_view: Entity<KnowledgebaseView>,
 }
 
 impl MemoryView {
     pub fn new(
-        berry_server_url: String,
+        client: BerryClient,
+        berry: &BerryConfig,
         personas: Vec<Persona>,
+        health: Entity<BerryHealthMonitor>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        let client = BerryClient::new(berry_server_url);
         let entity = cx.entity().clone();
+        let persona_ids: Vec<String> = personas.iter().map(|p| p.id.clone()).collect();
+        let knowledgebases: Vec<PersonaTab> = personas
+            .iter()
+            .filter_map(|p| {
+                p.knowledgebase_path.clone().map(|kb_path| PersonaTab {
+                    name: p.name.clone(),
+                    kb_path,
+                })
+            })
+            .collect();
+
+        // Browse as the UI or as any of the loaded personas
+        let mut actors = vec![UI_ACTOR.to_string()];
+        actors.extend(persona_ids.iter().filter(|id| *id != UI_ACTOR).cloned());
+        let profile = berry.active().clone();
+        let actor = profile
+            .default_actor
+            .clone()
+            .unwrap_or_else(|| UI_ACTOR.to_string());
+        if !actors.contains(&actor) {
+            actors.push(actor.clone());
+        }
 
         // Create Berry memory table with selection callback
         let entity_for_table = entity.clone();
         let table = cx.new(|_cx| {
             MemoryTable::new(vec![], move |_index, memory, _window, cx| {
                 entity_for_table.update(cx, |this, cx| {
-                    this.selected_memory = Some(memory.clone());
-                    this.slideout_open = true;
-                    this.slideout.update(cx, |slideout, cx| {
-                        slideout.set_memory(Some(memory.clone()));
-                        cx.notify();
-                    });
-                    cx.notify();
+                    this.open_memory(memory.clone(), cx);
                 });
             })
         });
 
+        // Show bulk actions whenever more than one row is selected
+        cx.subscribe(&table, |this, table, _event: &MemoryTableEvent, cx| {
+            let selected = table.read(cx).selection_count();
+            this.bulk_bar.update(cx, |bulk_bar, cx| {
+                bulk_bar.set_selected(selected);
+                cx.notify();
+            });
+            cx.notify();
+        })
+        .detach();
+
+        let bulk_bar = cx.new(|cx| BulkActionBar::new(window, cx));
+        cx.subscribe(
+            &bulk_bar,
+            |this, _bulk_bar, event: &BulkActionEvent, cx| match event {
+                BulkActionEvent::AddTags(tags) => {
+                    let actor = this.actor.clone();
+                    this.bulk_update(
+                        |memory| {
+                            let mut merged = memory.tags.clone();
+                            merged
+                                .extend(tags.iter().filter(|t| !memory.tags.contains(t)).cloned());
+                            UpdateMemoryRequest {
+                                as_actor: actor.clone(),
+                                tags: Some(merged),
+                                ..Default::default()
+                            }
+                        },
+                        cx,
+                    );
+                }
+                BulkActionEvent::RemoveTags(tags) => {
+                    let actor = this.actor.clone();
+                    this.bulk_update(
+                        |memory| UpdateMemoryRequest {
+                            as_actor: actor.clone(),
+                            tags: Some(
+                                memory
+                                    .tags
+                                    .iter()
+                                    .filter(|t| !tags.contains(t))
+                                    .cloned()
+                                    .collect(),
+                            ),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                }
+                BulkActionEvent::SetType(memory_type) => {
+                    let actor = this.actor.clone();
+                    this.bulk_update(
+                        |_memory| UpdateMemoryRequest {
+                            as_actor: actor.clone(),
+                            memory_type: Some(memory_type.clone()),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                }
+                BulkActionEvent::SetVisibility(visibility) => {
+                    let actor = this.actor.clone();
+                    this.bulk_update(
+                        |_memory| UpdateMemoryRequest {
+                            as_actor: actor.clone(),
+                            visibility: Some(visibility.clone()),
+                            // Only Shared keeps a list of actors, which bulk edits can't set
+                            shared_with: Some(vec![]),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                }
+                BulkActionEvent::Delete => this.bulk_delete(cx),
+                BulkActionEvent::SelectAll => {
+                    this.table.update(cx, |table, cx| {
+                        table.select_all();
+                        cx.emit(MemoryTableEvent::SelectionChanged);
+                        cx.notify();
+                    });
+                }
+                BulkActionEvent::ClearSelection => {
+                    this.table.update(cx, |table, cx| {
+                        table.clear_selection();
+                        cx.emit(MemoryTableEvent::SelectionChanged);
+                        cx.notify();
+                    });
+                }
+            },
+        )
+        .detach();
+
         // Create Berry slideout with close callback
         let entity_for_slideout = entity.clone();
-        let slideout = cx.new(|_cx| {
-            MemorySlideout::new(move |_window, cx| {
-                entity_for_slideout.update(cx, |this, cx| {
-                    this.slideout_open = false;
-                    this.selected_memory = None;
-                    this.table.update(cx, |table, cx| {
-                        table.set_selected(None);
+        let slideout_client = client.clone();
+        let slideout = cx.new(|cx| {
+            MemorySlideout::new(
+                slideout_client,
+                actor.clone(),
+                persona_ids,
+                knowledgebases,
+                move |_window, cx| {
+                    entity_for_slideout.update(cx, |this, cx| {
+                        this.slideout_open = false;
+                        this.selected_memory = None;
+                        this.table.update(cx, |table, cx| {
+                            table.set_selected(None);
+                            cx.notify();
+                        });
                         cx.notify();
                     });
-                    cx.notify();
-                });
-            })
+                },
+                cx,
+            )
         });
 
+        // Apply edits, deletes and promotions requested from the slideout
+        cx.subscribe_in(
+            &slideout,
+            window,
+            |this, _slideout, event: &MemorySlideoutEvent, window, cx| match event {
+                MemorySlideoutEvent::Update {
+                    id,
+                    content,
+                    memory_type,
+                    tags,
+                } => {
+                    this.update_memory(
+                        id.clone(),
+                        UpdateMemoryRequest {
+                            as_actor: this.actor.clone(),
+                            content: Some(content.clone()),
+                            memory_type: Some(memory_type.clone()),
+                            tags: Some(tags.clone()),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                }
+                MemorySlideoutEvent::Share {
+                    id,
+                    visibility,
+                    shared_with,
+                } => {
+                    this.update_memory(
+                        id.clone(),
+                        UpdateMemoryRequest {
+                            as_actor: this.actor.clone(),
+                            visibility: Some(visibility.clone()),
+                            shared_with: Some(shared_with.clone()),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                }
+                MemorySlideoutEvent::Delete { id } => {
+                    this.delete_memory(id.clone(), cx);
+                }
+                MemorySlideoutEvent::Promote {
+                    memory,
+                    kb_path,
+                    tag_source,
+                } => {
+                    this.promote_memory(memory, kb_path, *tag_source, window, cx);
+                }
+            },
+        )
+        .detach();
+
+        // Create composer for new memories
+        let composer = cx.new(|cx| MemoryComposer::new(window, cx));
+
+        cx.subscribe(
+            &composer,
+            |this, _composer, event: &MemoryComposerEvent, cx| match event {
+                MemoryComposerEvent::Submit {
+                    content,
+                    memory_type,
+                    tags,
+                    visibility,
+                    shared_with,
+                } => {
+                    this.create_memory(
+                        RememberRequest {
+                            content: content.clone(),
+                            created_by: this.actor.clone(),
+                            memory_type: memory_type.clone(),
+                            tags: tags.clone(),
+                            visibility: visibility.clone(),
+                            shared_with: shared_with.clone(),
+                        },
+                        cx,
+                    );
+                }
+                MemoryComposerEvent::Close => {
+                    this.composer_open = false;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        // Create export and import panel
+        let transfer = cx.new(|cx| MemoryTransferPanel::new(client.clone(), window, cx));
+
+        cx.subscribe(
+            &transfer,
+            |this, _transfer, event: &MemoryTransferEvent, cx| match event {
+                MemoryTransferEvent::Imported => this.fetch_memories(cx),
+                MemoryTransferEvent::Close => {
+                    this.transfer_open = false;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
         // Create knowledgebase view with all personas
-        let kb_view = cx.new(|cx| KnowledgebaseView::new(personas, cx));
+        let kb_view = cx.new(|cx| KnowledgebaseView::new(personas, window, cx));
 
         // Create search input
         let search_input =
@@ -127,15 +457,97 @@ impl MemoryView {
         )
         .detach();
 
+        // Re-run the search whenever a filter changes
+        let filter_bar = cx.new(|_cx| MemoryFilterBar::new());
+        cx.subscribe(
+            &filter_bar,
+            |this, _filter_bar, _event: &MemoryFilterBarEvent, cx| {
+                this.fetch_memories(cx);
+            },
+        )
+        .detach();
+
+        // Re-run the search as the newly selected actor
+        let actor_index = actors.iter().position(|a| *a == actor).map(IndexPath::new);
+        let actor_select = cx.new(|cx| SelectState::new(actors.clone(), actor_index, window, cx));
+        cx.subscribe(
+            &actor_select,
+            |this, _select, event: &SelectEvent<Vec<String>>, cx| {
+                if let SelectEvent::Confirm(Some(actor)) = event {
+                    this.set_actor(actor.clone(), cx);
+                }
+            },
+        )
+        .detach();
+
+        // Reconnect to the profile picked in the switcher
+        let profile_index = berry
+            .profiles
+            .iter()
+            .position(|p| p.name == profile.name)
+            .map(IndexPath::new);
+        let profile_select =
+            cx.new(|cx| SelectState::new(berry.profile_names(), profile_index, window, cx));
+        cx.subscribe_in(
+            &profile_select,
+            window,
+            |this, _select, event: &SelectEvent<Vec<String>>, window, cx| {
+                if let SelectEvent::Confirm(Some(name)) = event {
+                    this.switch_profile(name.clone(), window, cx);
+                }
+            },
+        )
+        .detach();
+
+        // Sync offline changes and reload once Berry comes back, and sync anything
+        // still queued the first time it is seen online, e.g. after being unauthorized
+        cx.observe(&health, |this, health, cx| {
+            let status = health.read(cx).status();
+            let online = matches!(status, BerryStatus::Online { .. });
+            let came_back = this.berry_offline && online;
+            let became_online = online && !this.berry_online;
+            this.berry_offline = status.is_offline();
+            this.berry_online = online;
+            if came_back || (became_online && !this.cache.pending().is_empty()) {
+                this.sync_pending(cx);
+            }
+            cx.notify();
+        })
+        .detach();
+
         let mut view = Self {
             selected_category: MemoryCategory::Berry,
             client,
+            profile: profile.name.clone(),
+            profile_select,
+            health,
+            berry_offline: false,
+            berry_online: false,
+            cache: MemoryCache::load_profile(&profile.name),
+            showing_cached: false,
+            syncing: false,
+            notice: None,
             memories: LoadingState::Idle,
+            total: 0,
+            loading_more: false,
+            load_more_error: None,
+            search_generation: 0,
             selected_memory: None,
             search_input: Some(search_input),
+            actor,
+            actors,
+            actor_select,
+            filter_bar,
             table,
+            layout: BerryLayout::Table,
+            timeline_grouping: TimelineGrouping::default(),
+            bulk_bar,
             slideout,
             slideout_open: false,
+            composer,
+            composer_open: false,
+            transfer,
+            transfer_open: false,
             kb_view,
         };
 
@@ -145,48 +557,493 @@ impl MemoryView {
         view
     }
 
+    fn search_request(&self, offset: usize, cx: &App) -> SearchRequest {
+        let query = self
+            .search_input
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default();
+
+        let mut request = SearchRequest {
+            query,
+            as_actor: self.actor.clone(),
+            limit: Some(PAGE_SIZE),
+            offset: (offset > 0).then_some(offset as u32),
+            ..Default::default()
+        };
+        self.filter_bar.read(cx).filters().apply(&mut request);
+        request
+    }
+
+    fn set_actor(&mut self, actor: String, cx: &mut Context<Self>) {
+        if actor == self.actor {
+            return;
+        }
+        self.actor = actor.clone();
+        self.slideout.update(cx, |slideout, cx| {
+            slideout.set_actor(actor, cx);
+        });
+        self.fetch_memories(cx);
+    }
+
+    /// Make another Berry profile active and reconnect to it
+    fn switch_profile(&mut self, name: String, window: &mut Window, cx: &mut Context<Self>) {
+        if name == self.profile {
+            return;
+        }
+
+        if AppConfig::load_file().berry.profile(&name).is_none() {
+            return;
+        }
+        if let Err(e) = AppConfig::update(|app_config| {
+            app_config.berry.set_active(&name);
+        }) {
+            eprintln!("Failed to save config: {}", e);
+        }
+
+        self.reload_berry(window, cx);
+        cx.emit(MemoryViewEvent::ProfileSwitched(name));
+    }
+
+    /// Rebuild the Berry client from the saved profiles and reload, switching the
+    /// cache and actor when the active profile changed
+    pub fn reload_berry(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let app_config = AppConfig::load();
+        let berry = &app_config.berry;
+        let profile = berry.active().clone();
+        let client = app_config.berry_client();
+
+        self.client = client.clone();
+        self.health.update(cx, |health, cx| {
+            health.set_client(client.clone(), cx);
+        });
+        self.slideout.update(cx, |slideout, cx| {
+            slideout.set_client(client.clone(), cx);
+        });
+        self.transfer.update(cx, |transfer, cx| {
+            transfer.set_client(client);
+            cx.notify();
+        });
+
+        let names = berry.profile_names();
+        let profile_index = berry
+            .profiles
+            .iter()
+            .position(|p| p.name == profile.name)
+            .map(IndexPath::new);
+        self.profile_select.update(cx, |select, cx| {
+            select.set_items(names, window, cx);
+            select.set_selected_index(profile_index, window, cx);
+        });
+
+        if profile.name != self.profile {
+            self.profile = profile.name.clone();
+            self.cache = MemoryCache::load_profile(&profile.name);
+            self.close_slideout(cx);
+            self.table.update(cx, |table, cx| {
+                table.clear_selection();
+                cx.emit(MemoryTableEvent::SelectionChanged);
+                cx.notify();
+            });
+
+            let actor = profile
+                .default_actor
+                .unwrap_or_else(|| UI_ACTOR.to_string());
+            if !self.actors.contains(&actor) {
+                self.actors.push(actor.clone());
+            }
+            let actor_index = self
+                .actors
+                .iter()
+                .position(|a| *a == actor)
+                .map(IndexPath::new);
+            let actors = self.actors.clone();
+            self.actor_select.update(cx, |select, cx| {
+                select.set_items(actors, window, cx);
+                select.set_selected_index(actor_index, window, cx);
+            });
+            self.actor = actor.clone();
+            self.slideout.update(cx, |slideout, cx| {
+                slideout.set_actor(actor, cx);
+            });
+        }
+
+        // The new server gets a fresh chance before falling back to the cache, and
+        // anything queued is synced once its first health check succeeds
+        self.berry_offline = false;
+        self.berry_online = false;
+        self.showing_cached = false;
+        self.notice = None;
+        self.fetch_memories(cx);
+    }
+
     fn fetch_memories(&mut self, cx: &mut Context<Self>) {
+        if self.berry_offline {
+            self.show_cached(cx);
+            return;
+        }
+
         self.memories = LoadingState::Loading;
+        self.loading_more = false;
+        self.load_more_error = None;
+        self.search_generation += 1;
         cx.notify();
 
         let entity = cx.entity().clone();
         let table = self.table.clone();
         let client = self.client.clone();
-        let query = self
-            .search_input
-            .as_ref()
-            .map(|input| input.read(cx).text().to_string())
-            .unwrap_or_default();
+        let request = self.search_request(0, cx);
+        let generation = self.search_generation;
 
-        let task = Tokio::spawn(cx, async move {
-            client
-                .search(SearchRequest {
-                    query,
-                    as_actor: "persona-ui".to_string(),
-                    limit: Some(25),
-                    ..Default::default()
-                })
-                .await
-        });
+        let task = Tokio::spawn(cx, async move { client.search(request).await });
 
         cx.spawn(async move |_this, cx| {
-            let result: Result<Result<Vec<Memory>, BerryError>, JoinError> = task.await;
+            let result: Result<Result<SearchPage, BerryError>, JoinError> = task.await;
             cx.update(|cx: &mut App| {
                 entity.update(cx, |this, cx| {
-                    match result {
-                        Ok(Ok(memories)) => {
-                            this.memories = LoadingState::Loaded(memories.clone());
+                    if this.search_generation != generation {
+                        return;
+                    }
+                    match SearchOutcome::from_result(result) {
+                        SearchOutcome::Page(page) => {
+                            this.showing_cached = false;
+                            this.total = page.total;
+                            this.store_in_cache(&page.memories);
+                            this.filter_bar.update(cx, |filter_bar, cx| {
+                                filter_bar.add_options(&page.memories);
+                                cx.notify();
+                            });
+                            this.memories = LoadingState::Loaded(page.memories.clone());
                             table.update(cx, |t, cx| {
-                                t.set_memories(memories);
+                                t.set_memories(page.memories);
                                 cx.notify();
                             });
                         }
-                        Ok(Err(e)) => {
-                            this.memories = LoadingState::Error(e.to_string());
+                        SearchOutcome::Unreachable => this.show_cached(cx),
+                        SearchOutcome::Unauthorized(error) => this.show_unauthorized(error, cx),
+                        SearchOutcome::Failed(error) => {
+                            this.memories = LoadingState::Error(error);
+                        }
+                    }
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    /// Fetch the next page for the current search and append it to the table
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        let LoadingState::Loaded(memories) = &self.memories else {
+            return;
+        };
+        if self.loading_more || memories.len() >= self.total {
+            return;
+        }
+
+        if self.showing_cached {
+            let page = self.cache.search(&self.search_request(memories.len(), cx));
+            self.append_page(page, cx);
+            return;
+        }
+
+        self.loading_more = true;
+        self.load_more_error = None;
+        cx.notify();
+
+        let entity = cx.entity().clone();
+        let table = self.table.clone();
+        let client = self.client.clone();
+        let request = self.search_request(memories.len(), cx);
+        let generation = self.search_generation;
+
+        let task = Tokio::spawn(cx, async move { client.search(request).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<SearchPage, BerryError>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    if this.search_generation != generation {
+                        return;
+                    }
+                    this.loading_more = false;
+                    match SearchOutcome::from_result(result) {
+                        SearchOutcome::Page(page) => {
+                            this.store_in_cache(&page.memories);
+                            this.append_page(page, cx);
+                        }
+                        SearchOutcome::Unreachable => this.show_cached(cx),
+                        SearchOutcome::Unauthorized(error) => {
+                            this.load_more_error = Some(error);
+                            this.health.update(cx, |health, cx| {
+                                health.check_now(cx);
+                            });
+                        }
+                        SearchOutcome::Failed(error) => this.load_more_error = Some(error),
+                    }
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn append_page(&mut self, page: SearchPage, cx: &mut Context<Self>) {
+        self.total = page.total;
+        let LoadingState::Loaded(memories) = &mut self.memories else {
+            return;
+        };
+
+        let new_memories: Vec<Memory> = page
+            .memories
+            .into_iter()
+            .filter(|m| !memories.iter().any(|existing| existing.id == m.id))
+            .collect();
+        // Nothing new means the server has no more to give
+        if new_memories.is_empty() {
+            self.total = memories.len();
+        }
+        memories.extend(new_memories.clone());
+        self.filter_bar.update(cx, |filter_bar, cx| {
+            filter_bar.add_options(&new_memories);
+            cx.notify();
+        });
+        self.table.update(cx, |t, cx| {
+            t.append_memories(new_memories);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// Show results from the local cache while Berry is unreachable
+    fn show_cached(&mut self, cx: &mut Context<Self>) {
+        let page = self.cache.search(&self.search_request(0, cx));
+
+        // Drop any live results still in flight
+        self.search_generation += 1;
+        self.showing_cached = true;
+        self.loading_more = false;
+        self.load_more_error = None;
+        self.total = page.total;
+        self.filter_bar.update(cx, |filter_bar, cx| {
+            filter_bar.add_options(&page.memories);
+            cx.notify();
+        });
+        self.memories = LoadingState::Loaded(page.memories.clone());
+        self.table.update(cx, |t, cx| {
+            t.set_memories(page.memories);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// Show an auth failure and refresh the health status so the footer reports it too
+    fn show_unauthorized(&mut self, error: String, cx: &mut Context<Self>) {
+        self.memories = LoadingState::Error(error);
+        self.health.update(cx, |health, cx| {
+            health.check_now(cx);
+        });
+    }
+
+    fn store_in_cache(&mut self, memories: &[Memory]) {
+        self.cache.store(memories);
+        self.save_cache();
+    }
+
+    fn save_cache(&self) {
+        if let Err(e) = self.cache.save() {
+            eprintln!("Failed to save memory cache: {}", e);
+        }
+    }
+
+    /// Replay changes queued while offline, then reload from the server
+    fn sync_pending(&mut self, cx: &mut Context<Self>) {
+        if self.syncing {
+            return;
+        }
+
+        let operations = self.cache.pending().to_vec();
+        if operations.is_empty() {
+            self.fetch_memories(cx);
+            return;
+        }
+
+        self.syncing = true;
+        self.notice = Some(format!("Syncing {} offline change(s)...", operations.len()));
+        cx.notify();
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let replayed = operations.len();
+
+        let task = Tokio::spawn(cx, async move { replay(&client, operations).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<SyncReport, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    this.syncing = false;
+                    match result {
+                        Ok(report) => {
+                            this.cache.finish_sync(replayed, &report);
+                            this.save_cache();
+                            this.notice = Some(report.summary());
                         }
                         Err(e) => {
-                            this.memories = LoadingState::Error(e.to_string());
+                            this.notice = Some(format!("Sync failed: {}", e));
+                        }
+                    }
+                    this.fetch_memories(cx);
+                });
+            })
+        })
+        .detach();
+    }
+
+    /// Show a memory in the slideout
+    fn open_memory(&mut self, memory: Memory, cx: &mut Context<Self>) {
+        self.selected_memory = Some(memory.clone());
+        self.slideout_open = true;
+        self.composer_open = false;
+        self.transfer_open = false;
+        self.slideout.update(cx, |slideout, cx| {
+            slideout.set_memory(Some(memory), cx);
+        });
+        cx.notify();
+    }
+
+    fn open_composer(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.composer.update(cx, |composer, cx| {
+            composer.reset(window, cx);
+        });
+        self.composer_open = true;
+        self.transfer_open = false;
+        self.slideout_open = false;
+        self.selected_memory = None;
+        self.table.update(cx, |table, cx| {
+            table.set_selected(None);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    fn open_transfer(&mut self, cx: &mut Context<Self>) {
+        let search = self.search_request(0, cx);
+        self.transfer.update(cx, |transfer, cx| {
+            transfer.set_search(search);
+            cx.notify();
+        });
+        self.transfer_open = true;
+        self.composer_open = false;
+        cx.notify();
+    }
+
+    fn create_memory(&mut self, request: RememberRequest, cx: &mut Context<Self>) {
+        if self.berry_offline {
+            self.queue_remember(request, cx);
+            return;
+        }
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let queued = request.clone();
+
+        let task = Tokio::spawn(cx, async move { client.remember(request).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<Memory, BerryError>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    let error = match result {
+                        Ok(Ok(_memory)) => {
+                            this.composer_open = false;
+                            this.fetch_memories(cx);
+                            None
+                        }
+                        Ok(Err(e)) if e.is_unreachable() => {
+                            this.queue_remember(queued, cx);
+                            None
+                        }
+                        Ok(Err(e)) => Some(e.to_string()),
+                        Err(e) => Some(e.to_string()),
+                    };
+                    this.composer.update(cx, |composer, cx| {
+                        composer.set_error(error);
+                        cx.notify();
+                    });
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn update_memory(&mut self, id: String, request: UpdateMemoryRequest, cx: &mut Context<Self>) {
+        if self.berry_offline {
+            self.queue_update(&id, request, cx);
+            return;
+        }
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let queued = (id.clone(), request.clone());
+
+        let task = Tokio::spawn(cx, async move { client.update_memory(&id, request).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<Memory, BerryError>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    match result {
+                        Ok(Ok(memory)) => {
+                            this.selected_memory = Some(memory.clone());
+                            this.slideout.update(cx, |slideout, cx| {
+                                slideout.set_memory(Some(memory), cx);
+                            });
+                            this.fetch_memories(cx);
+                        }
+                        Ok(Err(e)) if e.is_unreachable() => {
+                            let (id, request) = queued;
+                            this.queue_update(&id, request, cx);
+                        }
+                        Ok(Err(e)) => this.show_slideout_error(e.to_string(), cx),
+                        Err(e) => this.show_slideout_error(e.to_string(), cx),
+                    }
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn delete_memory(&mut self, id: String, cx: &mut Context<Self>) {
+        if self.berry_offline {
+            self.queue_forget(&id, cx);
+            return;
+        }
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let queued = id.clone();
+
+        let actor = self.actor.clone();
+
+        let task = Tokio::spawn(cx, async move { client.forget(&id, &actor).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<(), BerryError>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    match result {
+                        Ok(Ok(())) => {
+                            this.cache.forgotten(&queued);
+                            this.save_cache();
+                            this.close_slideout(cx);
+                            this.fetch_memories(cx);
                         }
+                        Ok(Err(e)) if e.is_unreachable() => this.queue_forget(&queued, cx),
+                        Ok(Err(e)) => this.show_slideout_error(e.to_string(), cx),
+                        Err(e) => this.show_slideout_error(e.to_string(), cx),
                     }
                     cx.notify();
                 });
@@ -195,6 +1052,250 @@ impl MemoryView {
         .detach();
     }
 
+    /// Write a memory into a persona's knowledgebase and open the new document
+    fn promote_memory(
+        &mut self,
+        memory: &Memory,
+        kb_path: &Path,
+        tag_source: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let path = match knowledgebase::create_file(
+            kb_path,
+            &document_title(memory),
+            &to_document(memory),
+        ) {
+            Ok(path) => path,
+            Err(e) => {
+                self.show_slideout_error(format!("Failed to promote memory: {}", e), cx);
+                return;
+            }
+        };
+
+        if tag_source && !memory.tags.iter().any(|t| t == PROMOTED_TAG) {
+            let mut tags = memory.tags.clone();
+            tags.push(PROMOTED_TAG.to_string());
+            self.update_memory(
+                memory.id.clone(),
+                UpdateMemoryRequest {
+                    as_actor: self.actor.clone(),
+                    tags: Some(tags),
+                    ..Default::default()
+                },
+                cx,
+            );
+        }
+
+        self.selected_category = MemoryCategory::Knowledgebase;
+        self.kb_view.update(cx, |kb_view, cx| {
+            kb_view.open_file(kb_path, &path, window, cx);
+        });
+        cx.notify();
+    }
+
+    fn queue_remember(&mut self, request: RememberRequest, cx: &mut Context<Self>) {
+        self.cache.queue_remember(request);
+        self.save_cache();
+        self.composer_open = false;
+        self.show_cached(cx);
+    }
+
+    fn queue_update(&mut self, id: &str, request: UpdateMemoryRequest, cx: &mut Context<Self>) {
+        let Some(memory) = self.cache.queue_update(id, request) else {
+            self.show_slideout_error("Memory is not available offline".to_string(), cx);
+            return;
+        };
+        self.save_cache();
+        self.selected_memory = Some(memory.clone());
+        self.slideout.update(cx, |slideout, cx| {
+            slideout.set_memory(Some(memory), cx);
+        });
+        self.show_cached(cx);
+    }
+
+    fn queue_forget(&mut self, id: &str, cx: &mut Context<Self>) {
+        self.cache.queue_forget(id, &self.actor);
+        self.save_cache();
+        self.close_slideout(cx);
+        self.show_cached(cx);
+    }
+
+    fn close_slideout(&mut self, cx: &mut Context<Self>) {
+        self.slideout_open = false;
+        self.selected_memory = None;
+        self.slideout.update(cx, |slideout, cx| {
+            slideout.set_memory(None, cx);
+        });
+    }
+
+    /// Apply an update built for each selected memory
+    fn bulk_update<F>(&mut self, build: F, cx: &mut Context<Self>)
+    where
+        F: Fn(&Memory) -> UpdateMemoryRequest,
+    {
+        let updates: Vec<(String, UpdateMemoryRequest)> = self
+            .table
+            .read(cx)
+            .selected_memories()
+            .iter()
+            .map(|memory| (memory.id.clone(), build(memory)))
+            .collect();
+        if updates.is_empty() {
+            return;
+        }
+
+        if self.berry_offline {
+            let total = updates.len();
+            let queued = updates
+                .into_iter()
+                .filter_map(|(id, request)| self.cache.queue_update(&id, request))
+                .count();
+            self.save_cache();
+            self.notice = Some(if queued == total {
+                format!("Queued changes to {} memories", queued)
+            } else {
+                format!(
+                    "Queued changes to {} memories, {} not available offline",
+                    queued,
+                    total - queued
+                )
+            });
+            self.show_cached(cx);
+            return;
+        }
+
+        let count = updates.len();
+        let client = self.client.clone();
+        self.run_bulk(
+            "Updated",
+            count,
+            async move {
+                let mut outcome = BulkOutcome::default();
+                for (id, request) in updates {
+                    match client.update_memory(&id, request).await {
+                        Ok(_) => outcome.done.push(id),
+                        Err(e) => outcome.failed.push(format!("{}: {}", id, e)),
+                    }
+                }
+                outcome
+            },
+            |_this, _done| {},
+            cx,
+        );
+    }
+
+    fn bulk_delete(&mut self, cx: &mut Context<Self>) {
+        let ids: Vec<String> = self
+            .table
+            .read(cx)
+            .selected_memories()
+            .into_iter()
+            .map(|memory| memory.id)
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+
+        self.close_slideout(cx);
+        self.table.update(cx, |table, cx| {
+            table.clear_selection();
+            cx.emit(MemoryTableEvent::SelectionChanged);
+            cx.notify();
+        });
+
+        if self.berry_offline {
+            let count = ids.len();
+            for id in &ids {
+                self.cache.queue_forget(id, &self.actor);
+            }
+            self.save_cache();
+            self.notice = Some(format!("Queued deletion of {} memories", count));
+            self.show_cached(cx);
+            return;
+        }
+
+        let count = ids.len();
+        let client = self.client.clone();
+        let actor = self.actor.clone();
+        self.run_bulk(
+            "Deleted",
+            count,
+            async move {
+                let mut outcome = BulkOutcome::default();
+                for id in ids {
+                    match client.forget(&id, &actor).await {
+                        Ok(()) => outcome.done.push(id),
+                        Err(e) => outcome.failed.push(format!("{}: {}", id, e)),
+                    }
+                }
+                outcome
+            },
+            |this, done| {
+                for id in done {
+                    this.cache.forgotten(id);
+                }
+                this.save_cache();
+            },
+            cx,
+        );
+    }
+
+    /// Run a bulk operation, then `on_done` with the memories it succeeded on
+    fn run_bulk(
+        &mut self,
+        verb: &'static str,
+        count: usize,
+        operation: impl std::future::Future<Output = BulkOutcome> + Send + 'static,
+        on_done: impl FnOnce(&mut Self, &[String]) + 'static,
+        cx: &mut Context<Self>,
+    ) {
+        self.bulk_bar.update(cx, |bulk_bar, cx| {
+            bulk_bar.set_busy(true);
+            cx.notify();
+        });
+
+        let entity = cx.entity().clone();
+        let task = Tokio::spawn(cx, operation);
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<BulkOutcome, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    this.bulk_bar.update(cx, |bulk_bar, cx| {
+                        bulk_bar.set_busy(false);
+                        cx.notify();
+                    });
+                    this.notice = Some(match result {
+                        Ok(outcome) => {
+                            on_done(this, &outcome.done);
+                            match outcome.failed.first() {
+                                None => format!("{} {} memories", verb, count),
+                                Some(first) => format!(
+                                    "{} {} memories, {} failed ({})",
+                                    verb,
+                                    outcome.done.len(),
+                                    outcome.failed.len(),
+                                    first
+                                ),
+                            }
+                        }
+                        Err(e) => format!("{} failed: {}", verb, e),
+                    });
+                    this.fetch_memories(cx);
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn show_slideout_error(&mut self, error: String, cx: &mut Context<Self>) {
+        self.slideout.update(cx, |slideout, cx| {
+            slideout.set_error(Some(error));
+            cx.notify();
+        });
+    }
+
     fn render_search_bar(&self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let search_input = self.search_input.clone();
 
@@ -202,6 +1303,7 @@ impl MemoryView {
             .w_full()
             .px_4()
             .py_3()
+            .gap_2()
             .border_b_1()
             .border_color(cx.theme().border)
             .when_some(search_input, |this, input| {
@@ -212,6 +1314,130 @@ impl MemoryView {
                         .appearance(false),
                 )
             })
+            .child(Select::new(&self.profile_select).small().w(px(160.)))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child("As"),
+                    )
+                    .child(Select::new(&self.actor_select).small().w(px(200.))),
+            )
+            .child(self.render_layout_toggle(cx))
+            .child(
+                Button::new("transfer-memories")
+                    .label("Export / Import")
+                    .ghost()
+                    .small()
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.open_transfer(cx);
+                    })),
+            )
+            .child(
+                Button::new("new-memory")
+                    .icon(IconName::Plus)
+                    .label("New Memory")
+                    .small()
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_composer(window, cx);
+                    })),
+            )
+    }
+
+    /// Switch between the table and the timeline, and pick the timeline's grouping
+    fn render_layout_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let timeline = self.layout == BerryLayout::Timeline;
+
+        h_flex()
+            .gap_1()
+            .child(
+                Button::new("layout-table")
+                    .icon(IconName::LayoutDashboard)
+                    .label("Table")
+                    .ghost()
+                    .small()
+                    .selected(!timeline)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.layout = BerryLayout::Table;
+                        cx.notify();
+                    })),
+            )
+            .child(
+                Button::new("layout-timeline")
+                    .icon(IconName::Calendar)
+                    .label("Timeline")
+                    .ghost()
+                    .small()
+                    .selected(timeline)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.layout = BerryLayout::Timeline;
+                        cx.notify();
+                    })),
+            )
+            .when(timeline, |this| {
+                this.children(TimelineGrouping::all().iter().map(|grouping| {
+                    let grouping = *grouping;
+                    Button::new(SharedString::from(format!(
+                        "timeline-{}",
+                        grouping.label().to_lowercase()
+                    )))
+                    .label(grouping.label())
+                    .outline()
+                    .xsmall()
+                    .selected(self.timeline_grouping == grouping)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.timeline_grouping = grouping;
+                        cx.notify();
+                    }))
+                }))
+            })
+    }
+
+    fn render_notice_banner(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let message = if self.showing_cached {
+            let pending = self.cache.pending().len();
+            let mut message = "Berry is offline, showing cached memories".to_string();
+            if pending > 0 {
+                message.push_str(&format!(" ({} change(s) waiting to sync)", pending));
+            }
+            message
+        } else {
+            self.notice.clone()?
+        };
+
+        let color = if self.showing_cached {
+            cx.theme().warning
+        } else {
+            cx.theme().muted_foreground
+        };
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_2()
+                .justify_between()
+                .items_center()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(div().text_sm().text_color(color).child(message))
+                .when(!self.showing_cached && !self.syncing, |this| {
+                    this.child(
+                        Button::new("dismiss-notice")
+                            .icon(IconName::Close)
+                            .ghost()
+                            .xsmall()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.notice = None;
+                                cx.notify();
+                            })),
+                    )
+                }),
+        )
     }
 
     fn render_berry_content(
@@ -230,15 +1456,7 @@ impl MemoryView {
                 .child("Loading memories...")
                 .into_any_element(),
 
-            LoadingState::Error(err) => div()
-                .flex_1()
-                .size_full()
-                .flex()
-                .items_center()
-                .justify_center()
-                .text_color(cx.theme().danger)
-                .child(format!("Error: {}", err))
-                .into_any_element(),
+            LoadingState::Error(err) => self.render_error(err, cx).into_any_element(),
 
             LoadingState::Loaded(memories) => {
                 if memories.is_empty() {
@@ -252,12 +1470,136 @@ impl MemoryView {
                         .child("No memories found")
                         .into_any_element()
                 } else {
-                    self.table.clone().into_any_element()
+                    let body = match self.layout {
+                        BerryLayout::Table => self.table.clone().into_any_element(),
+                        BerryLayout::Timeline => render_timeline(
+                            memories,
+                            self.timeline_grouping,
+                            self.selected_memory.as_ref().map(|m| m.id.as_str()),
+                            cx,
+                        )
+                        .into_any_element(),
+                    };
+                    v_flex()
+                        .flex_1()
+                        .size_full()
+                        .overflow_hidden()
+                        .child(body)
+                        .child(self.render_pagination(memories.len(), cx))
+                        .into_any_element()
                 }
             }
         }
     }
 
+    fn retry(&mut self, cx: &mut Context<Self>) {
+        self.health.update(cx, |health, cx| {
+            health.check_now(cx);
+        });
+        self.fetch_memories(cx);
+    }
+
+    fn render_error(&self, err: &str, cx: &mut Context<Self>) -> impl IntoElement {
+        let health = self.health.read(cx);
+        let (title, detail) = if health.status().is_offline() {
+            (
+                "Berry is offline".to_string(),
+                format!(
+                    "Could not reach the Berry server at {}. Check that Berry and ChromaDB are running.",
+                    health.server_url()
+                ),
+            )
+        } else if matches!(health.status(), BerryStatus::Unauthorized(_)) {
+            (
+                "Berry rejected the credentials".to_string(),
+                format!(
+                    "{}. Check the auth settings and token for {} in Memory settings.",
+                    err,
+                    health.server_url()
+                ),
+            )
+        } else {
+            ("Failed to load memories".to_string(), err.to_string())
+        };
+
+        v_flex()
+            .flex_1()
+            .size_full()
+            .items_center()
+            .justify_center()
+            .gap_2()
+            .child(
+                div()
+                    .text_base()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(cx.theme().danger)
+                    .child(title),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(detail),
+            )
+            .child(
+                Button::new("retry-memories")
+                    .label("Retry")
+                    .small()
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.retry(cx);
+                    })),
+            )
+    }
+
+    fn render_pagination(&self, loaded: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_more = loaded < self.total;
+
+        h_flex()
+            .w_full()
+            .px_4()
+            .py_2()
+            .justify_between()
+            .items_center()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("{} of {}", loaded, self.total.max(loaded))),
+            )
+            .when(has_more, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .when_some(self.load_more_error.clone(), |this, error| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().danger)
+                                    .child(format!("Failed to load more: {}", error)),
+                            )
+                        })
+                        .child(
+                            Button::new("load-more-memories")
+                                .label(if self.loading_more {
+                                    "Loading..."
+                                } else if self.load_more_error.is_some() {
+                                    "Retry"
+                                } else {
+                                    "Load More"
+                                })
+                                .small()
+                                .disabled(self.loading_more)
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.load_more(cx);
+                                })),
+                        ),
+                )
+            })
+    }
+
     fn render_category_list(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         let categories = MemoryCategory::all();
 
@@ -321,10 +1663,19 @@ impl MemoryView {
                         .flex_1()
                         .h_full()
                         .child(self.render_search_bar(window, cx))
+                        .child(self.filter_bar.clone())
+                        .when(self.table.read(cx).selection_count() > 1, |this| {
+                            this.child(self.bulk_bar.clone())
+                        })
+                        .children(self.render_notice_banner(cx))
                         .child(self.render_berry_content(window, cx)),
                 );
 
-                if self.slideout_open {
+                if self.composer_open {
+                    content = content.child(self.composer.clone());
+                } else if self.transfer_open {
+                    content = content.child(self.transfer.clone());
+                } else if self.slideout_open {
                     content = content.child(self.slideout.clone());
                 }
 
@@ -343,3 +1694,83 @@ impl Render for MemoryView {
             .child(self.render_main_content(window, cx))
     }
 }
+
+/// Split a comma separated list, dropping empty entries
+fn parse_list(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::mock::{block_on, fixture_memory, MockBerryServer};
+
+    fn search(client: &BerryClient) -> SearchOutcome {
+        let result = block_on(client.search(SearchRequest {
+            as_actor: UI_ACTOR.to_string(),
+            limit: Some(PAGE_SIZE),
+            ..Default::default()
+        }));
+        SearchOutcome::from_result(Ok(result))
+    }
+
+    #[test]
+    fn test_search_outcome_page() {
+        let server = MockBerryServer::start(vec![
+            fixture_memory("mem-1", "First"),
+            fixture_memory("mem-2", "Second"),
+        ]);
+        let client = BerryClient::new(server.url());
+
+        match search(&client) {
+            SearchOutcome::Page(page) => {
+                assert_eq!(page.total, 2);
+                assert_eq!(page.memories[0].id, "mem-1");
+            }
+            other => panic!("Expected a page, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_search_outcome_server_error() {
+        let server = MockBerryServer::start(vec![]);
+        server.fail_next(503, "ChromaDB is not running");
+        let client = BerryClient::new(server.url());
+
+        match search(&client) {
+            SearchOutcome::Failed(error) => assert!(error.contains("ChromaDB is not running")),
+            other => panic!("Expected a failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_search_outcome_unauthorized() {
+        let server = MockBerryServer::start(vec![]);
+        server.fail_next(401, "Invalid token");
+        let client = BerryClient::new(server.url());
+
+        match search(&client) {
+            SearchOutcome::Unauthorized(error) => assert!(error.contains("Invalid token")),
+            other => panic!("Expected an auth failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_search_outcome_unreachable() {
+        let server = MockBerryServer::start(vec![]);
+        let client = BerryClient::new(server.url());
+        drop(server);
+
+        assert!(matches!(search(&client), SearchOutcome::Unreachable));
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list(" a, b ,,c "), vec!["a", "b", "c"]);
+        assert!(parse_list("").is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::memory::{BerryClient, BerryError, Memory, SearchPage, SearchRequest};
+use gpui::*;
+use gpui_component::{h_flex, v_flex, ActiveTheme};
+use gpui_tokio_bridge::{JoinError, Tokio};
+
+use super::badge::type_badge;
+use super::LoadingState;
+
+/// Number of related memories listed
+const RELATED_LIMIT: usize = 5;
+
+/// Words of the memory's content used to seed the search
+const QUERY_WORDS: usize = 40;
+
+#[derive(Debug, Clone)]
+pub enum RelatedMemoriesEvent {
+    Open(Memory),
+}
+
+impl EventEmitter<RelatedMemoriesEvent> for RelatedMemories {}
+
+/// Memories Berry returns when searching with another memory's content and tags
+pub struct RelatedMemories {
+    client: BerryClient,
+    actor: String,
+    related: LoadingState<Vec<Memory>>,
+    /// Incremented for every search so results for a previous memory are dropped
+    generation: usize,
+}
+
+impl RelatedMemories {
+    pub fn new(client: BerryClient, actor: String) -> Self {
+        Self {
+            client,
+            actor,
+            related: LoadingState::Idle,
+            generation: 0,
+        }
+    }
+
+    pub fn set_actor(&mut self, actor: String) {
+        self.actor = actor;
+    }
+
+    pub fn set_client(&mut self, client: BerryClient) {
+        self.client = client;
+        self.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.generation += 1;
+        self.related = LoadingState::Idle;
+    }
+
+    pub fn load(&mut self, memory: &Memory, cx: &mut Context<Self>) {
+        self.generation += 1;
+        self.related = LoadingState::Loading;
+        cx.notify();
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let request = SearchRequest {
+            query: related_query(memory),
+            as_actor: self.actor.clone(),
+            // One extra in case the memory finds itself
+            limit: Some(RELATED_LIMIT as u32 + 1),
+            ..Default::default()
+        };
+        let memory_id = memory.id.clone();
+        let generation = self.generation;
+
+        let task = Tokio::spawn(cx, async move { client.search(request).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<SearchPage, BerryError>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    if this.generation != generation {
+                        return;
+                    }
+                    this.related = match result {
+                        Ok(Ok(page)) => LoadingState::Loaded(related_matches(
+                            &memory_id,
+                            page.memories,
+                            RELATED_LIMIT,
+                        )),
+                        Ok(Err(e)) => LoadingState::Error(e.to_string()),
+                        Err(e) => LoadingState::Error(e.to_string()),
+                    };
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn render_match(&self, index: usize, memory: &Memory, cx: &mut Context<Self>) -> Stateful<Div> {
+        let preview = memory
+            .content
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let opened = memory.clone();
+
+        v_flex()
+            .id(("related-memory", index))
+            .w_full()
+            .p_2()
+            .gap_1()
+            .rounded_md()
+            .cursor_pointer()
+            .hover(|style| style.bg(cx.theme().muted))
+            .child(div().w_full().text_sm().truncate().child(preview))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(type_badge(&memory.memory_type, cx))
+                    .child(memory.created_by.clone())
+                    .child(memory.created_at.format("%Y-%m-%d").to_string()),
+            )
+            .on_click(cx.listener(move |_this, _, _window, cx| {
+                cx.emit(RelatedMemoriesEvent::Open(opened.clone()));
+            }))
+    }
+}
+
+impl Render for RelatedMemories {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let message = |text: String| {
+            div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(text)
+        };
+
+        match &self.related {
+            LoadingState::Idle => div().into_any_element(),
+            LoadingState::Loading => message("Searching...".to_string()).into_any_element(),
+            LoadingState::Error(error) => {
+                message(format!("Could not load related memories: {}", error)).into_any_element()
+            }
+            LoadingState::Loaded(memories) if memories.is_empty() => {
+                message("No related memories found".to_string()).into_any_element()
+            }
+            LoadingState::Loaded(memories) => {
+                let rows: Vec<_> = memories
+                    .iter()
+                    .enumerate()
+                    .map(|(index, memory)| self.render_match(index, memory, cx))
+                    .collect();
+                v_flex().w_full().children(rows).into_any_element()
+            }
+        }
+    }
+}
+
+/// Search query built from the start of a memory's content followed by its tags
+fn related_query(memory: &Memory) -> String {
+    memory
+        .content
+        .split_whitespace()
+        .take(QUERY_WORDS)
+        .chain(memory.tags.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Search results without the memory they were found for
+fn related_matches(memory_id: &str, memories: Vec<Memory>, limit: usize) -> Vec<Memory> {
+    memories
+        .into_iter()
+        .filter(|memory| memory.id != memory_id)
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::mock::MemoryBuilder;
+
+    #[test]
+    fn test_related_query_uses_content_and_tags() {
+        let source = MemoryBuilder::new("m1", "Review the\n  release checklist")
+            .tags(&["release", "qa"])
+            .build();
+
+        assert_eq!(
+            related_query(&source),
+            "Review the release checklist release qa"
+        );
+    }
+
+    #[test]
+    fn test_related_query_limits_content_words() {
+        let content = ["word"; QUERY_WORDS + 10].join(" ");
+        let source = MemoryBuilder::new("m1", &content).tags(&[]).build();
+
+        assert_eq!(related_query(&source).split(' ').count(), QUERY_WORDS);
+    }
+
+    #[test]
+    fn test_related_matches_excludes_source() {
+        let results = vec![
+            MemoryBuilder::new("m1", "source").build(),
+            MemoryBuilder::new("m2", "a").build(),
+            MemoryBuilder::new("m3", "b").build(),
+            MemoryBuilder::new("m4", "c").build(),
+        ];
+
+        let ids: Vec<String> = related_matches("m1", results, 2)
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec!["m2", "m3"]);
+    }
+}
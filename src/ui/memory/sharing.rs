@@ -0,0 +1,188 @@
+use crate::memory::Visibility;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    select::{Select, SelectState},
+    v_flex, ActiveTheme, IndexPath, Selectable, Sizable,
+};
+
+use super::parse_list;
+
+#[derive(Debug, Clone)]
+pub enum SharingEditorEvent {
+    Save {
+        visibility: Visibility,
+        shared_with: Vec<String>,
+    },
+    Cancel,
+}
+
+impl EventEmitter<SharingEditorEvent> for SharingEditor {}
+
+/// Editor for the visibility of a memory and the actors it is shared with
+pub struct SharingEditor {
+    /// Kept when nothing is picked, so a visibility not offered in the list survives a save
+    visibility: Visibility,
+    visibility_select: Entity<SelectState<Vec<String>>>,
+    /// Actor IDs that can be toggled on and off, usually the loaded personas
+    known_actors: Vec<String>,
+    selected_actors: Vec<String>,
+    other_actors_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+impl SharingEditor {
+    pub fn new(
+        visibility: Visibility,
+        shared_with: &[String],
+        known_actors: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let visibility_names: Vec<String> =
+            Visibility::all().iter().map(|v| v.to_string()).collect();
+        let visibility_index = Visibility::all()
+            .iter()
+            .position(|v| *v == visibility)
+            .map(IndexPath::new);
+        let visibility_select =
+            cx.new(|cx| SelectState::new(visibility_names, visibility_index, window, cx));
+
+        cx.observe(&visibility_select, |_this, _select, cx| cx.notify())
+            .detach();
+
+        // Actors that aren't known personas are edited as free text
+        let (selected_actors, other_actors): (Vec<String>, Vec<String>) = shared_with
+            .iter()
+            .cloned()
+            .partition(|actor| known_actors.contains(actor));
+
+        let other_actors_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Other actor IDs, comma separated...")
+                .default_value(other_actors.join(", "))
+        });
+
+        Self {
+            visibility,
+            visibility_select,
+            known_actors,
+            selected_actors,
+            other_actors_input,
+            error: None,
+        }
+    }
+
+    fn selected_visibility(&self, cx: &App) -> Visibility {
+        self.visibility_select
+            .read(cx)
+            .selected_value()
+            .and_then(|name| Visibility::all().iter().find(|v| v.to_string() == *name))
+            .cloned()
+            .unwrap_or_else(|| self.visibility.clone())
+    }
+
+    fn toggle_actor(&mut self, actor: &str, cx: &mut Context<Self>) {
+        if let Some(index) = self.selected_actors.iter().position(|a| a == actor) {
+            self.selected_actors.remove(index);
+        } else {
+            self.selected_actors.push(actor.to_string());
+        }
+        cx.notify();
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let visibility = self.selected_visibility(cx);
+
+        // Unknown visibilities may still depend on who the memory is shared with
+        let shared_with = if matches!(visibility, Visibility::Shared | Visibility::Other(_)) {
+            let mut actors = self.selected_actors.clone();
+            for actor in parse_list(&self.other_actors_input.read(cx).text().to_string()) {
+                if !actors.contains(&actor) {
+                    actors.push(actor);
+                }
+            }
+            actors
+        } else {
+            vec![]
+        };
+
+        if visibility == Visibility::Shared && shared_with.is_empty() {
+            self.error = Some("Shared memories need at least one actor".to_string());
+            cx.notify();
+            return;
+        }
+
+        self.error = None;
+        cx.emit(SharingEditorEvent::Save {
+            visibility,
+            shared_with,
+        });
+    }
+}
+
+impl Render for SharingEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_shared = self.selected_visibility(cx) == Visibility::Shared;
+
+        let actor_buttons: Vec<_> = self
+            .known_actors
+            .iter()
+            .enumerate()
+            .map(|(index, actor)| {
+                let actor_id = actor.clone();
+                Button::new(("share-actor", index))
+                    .label(actor.clone())
+                    .xsmall()
+                    .outline()
+                    .selected(self.selected_actors.contains(actor))
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.toggle_actor(&actor_id, cx);
+                    }))
+            })
+            .collect();
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .p_3()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().border)
+            .child(Select::new(&self.visibility_select).w(px(200.)))
+            .when(is_shared, |this| {
+                this.when(!actor_buttons.is_empty(), |this| {
+                    this.child(h_flex().flex_wrap().gap_1().children(actor_buttons))
+                })
+                .child(Input::new(&self.other_actors_input).w_full())
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+            })
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("save-sharing")
+                            .label("Save Sharing")
+                            .primary()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.save(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("cancel-sharing")
+                            .label("Cancel")
+                            .ghost()
+                            .small()
+                            .on_click(cx.listener(|_this, _, _window, cx| {
+                                cx.emit(SharingEditorEvent::Cancel);
+                            })),
+                    ),
+            )
+    }
+}
@@ -1,35 +1,490 @@
-use crate::memory::Memory;
+use crate::memory::transfer::PROMOTED_TAG;
+use crate::memory::{BerryClient, Memory, MemoryType, Visibility};
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::{
     button::{Button, ButtonVariants},
-    h_flex, v_flex, ActiveTheme, IconName, Sizable,
+    h_flex,
+    input::{Input, InputState},
+    select::{Select, SelectState},
+    text::TextView,
+    v_flex, ActiveTheme, Disableable, IconName, IndexPath, Selectable, Sizable,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use super::badge::type_badge;
+use super::knowledgebase_view::PersonaTab;
+use super::parse_list;
+use super::related::{RelatedMemories, RelatedMemoriesEvent};
+use super::sharing::{SharingEditor, SharingEditorEvent};
+
+#[derive(Debug, Clone)]
+pub enum MemorySlideoutEvent {
+    Update {
+        id: String,
+        content: String,
+        memory_type: MemoryType,
+        tags: Vec<String>,
+    },
+    Share {
+        id: String,
+        visibility: Visibility,
+        shared_with: Vec<String>,
+    },
+    Delete {
+        id: String,
+    },
+    Promote {
+        memory: Memory,
+        kb_path: PathBuf,
+        tag_source: bool,
+    },
+}
+
+impl EventEmitter<MemorySlideoutEvent> for MemorySlideout {}
+
+/// Inputs used while the memory is being edited
+struct MemoryEditor {
+    content_input: Entity<InputState>,
+    tags_input: Entity<InputState>,
+    type_select: Entity<SelectState<Vec<String>>>,
+}
+
+/// Choice of knowledgebase a memory is promoted into
+struct PromoteForm {
+    persona_select: Entity<SelectState<Vec<String>>>,
+    /// Tag the memory as promoted once the document is written
+    tag_source: bool,
+}
+
 pub struct MemorySlideout {
     memory: Option<Memory>,
+    /// Memories navigated away from through related memories
+    back: Vec<Memory>,
+    forward: Vec<Memory>,
+    related: Entity<RelatedMemories>,
+    editor: Option<MemoryEditor>,
+    sharing_editor: Option<Entity<SharingEditor>>,
+    /// Actor IDs offered in the sharing editor
+    known_actors: Vec<String>,
+    /// Persona knowledgebases memories can be promoted into
+    knowledgebases: Vec<PersonaTab>,
+    promote_form: Option<PromoteForm>,
+    confirm_delete: bool,
+    /// Show the Markdown source instead of the rendered document
+    show_raw: bool,
+    error: Option<String>,
     on_close: Arc<dyn Fn(&mut Window, &mut App) + Send + Sync + 'static>,
 }
 
 impl MemorySlideout {
-    pub fn new<F>(on_close: F) -> Self
+    pub fn new<F>(
+        client: BerryClient,
+        actor: String,
+        known_actors: Vec<String>,
+        knowledgebases: Vec<PersonaTab>,
+        on_close: F,
+        cx: &mut Context<Self>,
+    ) -> Self
     where
         F: Fn(&mut Window, &mut App) + Send + Sync + 'static,
     {
+        let related = cx.new(|_cx| RelatedMemories::new(client, actor));
+        cx.subscribe(
+            &related,
+            |this, _related, event: &RelatedMemoriesEvent, cx| match event {
+                RelatedMemoriesEvent::Open(memory) => this.navigate_to(memory.clone(), cx),
+            },
+        )
+        .detach();
+
         Self {
             memory: None,
+            back: Vec::new(),
+            forward: Vec::new(),
+            related,
+            editor: None,
+            sharing_editor: None,
+            known_actors,
+            knowledgebases,
+            promote_form: None,
+            confirm_delete: false,
+            show_raw: false,
+            error: None,
             on_close: Arc::new(on_close),
         }
     }
 
-    pub fn set_memory(&mut self, memory: Option<Memory>) {
+    /// Show a memory opened from outside the slideout, or refresh the current one
+    ///
+    /// History is kept when the same memory is shown again after an edit.
+    pub fn set_memory(&mut self, memory: Option<Memory>, cx: &mut Context<Self>) {
+        let same = matches!((&self.memory, &memory), (Some(a), Some(b)) if a.id == b.id);
+        if !same {
+            self.back.clear();
+            self.forward.clear();
+        }
+        self.show(memory, cx);
+    }
+
+    pub fn set_actor(&mut self, actor: String, cx: &mut Context<Self>) {
+        self.related
+            .update(cx, |related, _cx| related.set_actor(actor));
+    }
+
+    pub fn set_client(&mut self, client: BerryClient, cx: &mut Context<Self>) {
+        self.related
+            .update(cx, |related, _cx| related.set_client(client));
+    }
+
+    fn show(&mut self, memory: Option<Memory>, cx: &mut Context<Self>) {
+        self.related.update(cx, |related, cx| match &memory {
+            Some(memory) => related.load(memory, cx),
+            None => related.clear(),
+        });
         self.memory = memory;
+        self.editor = None;
+        self.sharing_editor = None;
+        self.promote_form = None;
+        self.confirm_delete = false;
+        self.show_raw = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    /// Open a related memory, remembering the current one for going back
+    fn navigate_to(&mut self, memory: Memory, cx: &mut Context<Self>) {
+        if let Some(current) = self.memory.take() {
+            self.back.push(current);
+        }
+        self.forward.clear();
+        self.show(Some(memory), cx);
+    }
+
+    fn go_back(&mut self, cx: &mut Context<Self>) {
+        let Some(previous) = self.back.pop() else {
+            return;
+        };
+        if let Some(current) = self.memory.take() {
+            self.forward.push(current);
+        }
+        self.show(Some(previous), cx);
+    }
+
+    fn go_forward(&mut self, cx: &mut Context<Self>) {
+        let Some(next) = self.forward.pop() else {
+            return;
+        };
+        if let Some(current) = self.memory.take() {
+            self.back.push(current);
+        }
+        self.show(Some(next), cx);
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
+    fn start_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(memory) = &self.memory else {
+            return;
+        };
+
+        let content_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .default_value(&memory.content)
+        });
+
+        let tags_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Comma separated tags...")
+                .default_value(memory.tags.join(", "))
+        });
+
+        // Offer the memory's own type too when it isn't one of the known types
+        let mut types = MemoryType::all().to_vec();
+        if !types.contains(&memory.memory_type) {
+            types.push(memory.memory_type.clone());
+        }
+        let type_names: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+        let current_type_index = types
+            .iter()
+            .position(|t| t == &memory.memory_type)
+            .map(IndexPath::new);
+        let type_select = cx.new(|cx| SelectState::new(type_names, current_type_index, window, cx));
+
+        self.editor = Some(MemoryEditor {
+            content_input,
+            tags_input,
+            type_select,
+        });
+        self.promote_form = None;
+        self.confirm_delete = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn cancel_edit(&mut self, cx: &mut Context<Self>) {
+        self.editor = None;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn save_edit(&mut self, cx: &mut Context<Self>) {
+        let (Some(memory), Some(editor)) = (&self.memory, &self.editor) else {
+            return;
+        };
+
+        let id = memory.id.clone();
+        let content = editor.content_input.read(cx).text().to_string();
+        let tags = parse_list(&editor.tags_input.read(cx).text().to_string());
+        let memory_type = editor
+            .type_select
+            .read(cx)
+            .selected_value()
+            .map(|name| MemoryType::parse(name))
+            .unwrap_or_else(|| memory.memory_type.clone());
+
+        if content.trim().is_empty() {
+            self.error = Some("Content cannot be empty".to_string());
+            cx.notify();
+            return;
+        }
+
+        cx.emit(MemorySlideoutEvent::Update {
+            id,
+            content,
+            memory_type,
+            tags,
+        });
+    }
+
+    fn start_sharing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(memory) = &self.memory else {
+            return;
+        };
+
+        let visibility = memory.visibility.clone();
+        let shared_with = memory.shared_with.clone();
+        let known_actors = self.known_actors.clone();
+        let sharing_editor =
+            cx.new(|cx| SharingEditor::new(visibility, &shared_with, known_actors, window, cx));
+
+        cx.subscribe(
+            &sharing_editor,
+            |this, _editor, event: &SharingEditorEvent, cx| match event {
+                SharingEditorEvent::Save {
+                    visibility,
+                    shared_with,
+                } => {
+                    if let Some(memory) = &this.memory {
+                        cx.emit(MemorySlideoutEvent::Share {
+                            id: memory.id.clone(),
+                            visibility: visibility.clone(),
+                            shared_with: shared_with.clone(),
+                        });
+                    }
+                }
+                SharingEditorEvent::Cancel => {
+                    this.sharing_editor = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        self.sharing_editor = Some(sharing_editor);
+        self.editor = None;
+        self.promote_form = None;
+        self.confirm_delete = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn start_promote(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let names: Vec<String> = self
+            .knowledgebases
+            .iter()
+            .map(|kb| kb.name.clone())
+            .collect();
+        let persona_select =
+            cx.new(|cx| SelectState::new(names, Some(IndexPath::new(0)), window, cx));
+
+        self.promote_form = Some(PromoteForm {
+            persona_select,
+            tag_source: true,
+        });
+        self.editor = None;
+        self.sharing_editor = None;
+        self.confirm_delete = false;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn promote(&mut self, cx: &mut Context<Self>) {
+        let (Some(memory), Some(form)) = (&self.memory, &self.promote_form) else {
+            return;
+        };
+
+        let selected = form.persona_select.read(cx).selected_value().cloned();
+        let Some(knowledgebase) =
+            selected.and_then(|name| self.knowledgebases.iter().find(|kb| kb.name == name))
+        else {
+            self.error = Some("Choose a knowledgebase".to_string());
+            cx.notify();
+            return;
+        };
+
+        cx.emit(MemorySlideoutEvent::Promote {
+            memory: memory.clone(),
+            kb_path: knowledgebase.kb_path.clone(),
+            tag_source: form.tag_source,
+        });
+        self.promote_form = None;
+        cx.notify();
+    }
+
+    fn render_promote_form(&self, form: &PromoteForm, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(Select::new(&form.persona_select).small().w_full())
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("promote-tag-source")
+                            .label(format!("Tag as \"{}\"", PROMOTED_TAG))
+                            .outline()
+                            .xsmall()
+                            .selected(form.tag_source)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                if let Some(form) = &mut this.promote_form {
+                                    form.tag_source = !form.tag_source;
+                                }
+                                cx.notify();
+                            })),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("promote-memory-confirm")
+                            .label("Promote")
+                            .primary()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.promote(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("promote-memory-cancel")
+                            .label("Cancel")
+                            .ghost()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.promote_form = None;
+                                cx.notify();
+                            })),
+                    ),
+            )
+    }
+
+    fn delete(&mut self, cx: &mut Context<Self>) {
+        let Some(memory) = &self.memory else {
+            return;
+        };
+
+        if !self.confirm_delete {
+            self.confirm_delete = true;
+            cx.notify();
+            return;
+        }
+
+        cx.emit(MemorySlideoutEvent::Delete {
+            id: memory.id.clone(),
+        });
     }
 
     fn render_header(&self, memory: &Memory, cx: &mut Context<Self>) -> impl IntoElement {
         let entity = cx.entity().clone();
         let id_preview: String = memory.id.chars().take(12).collect();
 
+        let actions =
+            if self.editor.is_some() {
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("save-memory")
+                            .label("Save")
+                            .primary()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.save_edit(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("cancel-edit-memory")
+                            .label("Cancel")
+                            .ghost()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.cancel_edit(cx);
+                            })),
+                    )
+            } else {
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("edit-memory")
+                            .label("Edit")
+                            .small()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_edit(window, cx);
+                            })),
+                    )
+                    .child(Button::new("share-memory").label("Share").small().on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.start_sharing(window, cx);
+                        }),
+                    ))
+                    .when(!self.knowledgebases.is_empty(), |this| {
+                        this.child(
+                            Button::new("promote-memory")
+                                .label("Promote")
+                                .small()
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.start_promote(window, cx);
+                                })),
+                        )
+                    })
+                    .child(
+                        Button::new("delete-memory")
+                            .label(if self.confirm_delete {
+                                "Confirm Delete"
+                            } else {
+                                "Delete"
+                            })
+                            .danger()
+                            .small()
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.delete(cx);
+                            })),
+                    )
+                    .when(self.confirm_delete, |this| {
+                        this.child(
+                            Button::new("cancel-delete-memory")
+                                .label("Cancel")
+                                .ghost()
+                                .small()
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.confirm_delete = false;
+                                    cx.notify();
+                                })),
+                        )
+                    })
+            };
+
         h_flex()
             .w_full()
             .px_4()
@@ -38,20 +493,47 @@ impl MemorySlideout {
             .border_color(cx.theme().border)
             .justify_between()
             .child(
-                div()
-                    .text_base()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child(format!("Memory: {}...", id_preview)),
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        Button::new("memory-back")
+                            .icon(IconName::ArrowLeft)
+                            .ghost()
+                            .xsmall()
+                            .disabled(self.back.is_empty())
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.go_back(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("memory-forward")
+                            .icon(IconName::ArrowRight)
+                            .ghost()
+                            .xsmall()
+                            .disabled(self.forward.is_empty())
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.go_forward(cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_base()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(format!("Memory: {}...", id_preview)),
+                    ),
             )
             .child(
-                Button::new("close-slideout")
-                    .icon(IconName::Close)
-                    .ghost()
-                    .xsmall()
-                    .on_click(move |_, window, cx| {
-                        let on_close = entity.read(cx).on_close.clone();
-                        on_close(window, cx);
-                    }),
+                h_flex().gap_2().child(actions).child(
+                    Button::new("close-slideout")
+                        .icon(IconName::Close)
+                        .ghost()
+                        .xsmall()
+                        .on_click(move |_, window, cx| {
+                            let on_close = entity.read(cx).on_close.clone();
+                            on_close(window, cx);
+                        }),
+                ),
             )
     }
 
@@ -68,7 +550,108 @@ impl MemorySlideout {
             .child(title.into())
     }
 
-    fn render_content(&self, memory: &Memory, cx: &mut Context<Self>) -> impl IntoElement {
+    /// Raw/rendered toggle and copy button shown above the document
+    fn render_document_toolbar(&self, memory: &Memory, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = memory.content.clone();
+
+        h_flex()
+            .gap_1()
+            .child(
+                Button::new("toggle-raw-content")
+                    .label("Raw")
+                    .ghost()
+                    .xsmall()
+                    .selected(self.show_raw)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.show_raw = !this.show_raw;
+                        cx.notify();
+                    })),
+            )
+            .child(
+                Button::new("copy-content")
+                    .icon(IconName::Copy)
+                    .label("Copy")
+                    .ghost()
+                    .xsmall()
+                    .on_click(move |_, _window, cx| {
+                        cx.write_to_clipboard(ClipboardItem::new_string(content.clone()));
+                    }),
+            )
+    }
+
+    fn render_document(
+        &self,
+        memory: &Memory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        if let Some(editor) = &self.editor {
+            return Input::new(&editor.content_input)
+                .w_full()
+                .h(px(240.))
+                .into_any_element();
+        }
+
+        let container = div()
+            .w_full()
+            .p_3()
+            .rounded_md()
+            .bg(cx.theme().sidebar)
+            .text_sm();
+
+        if self.show_raw {
+            return container
+                .font_family("monospace")
+                .text_color(cx.theme().muted_foreground)
+                .child(memory.content.clone())
+                .into_any_element();
+        }
+
+        container
+            .child(TextView::markdown(
+                "memory-content",
+                memory.content.clone(),
+                window,
+                cx,
+            ))
+            .into_any_element()
+    }
+
+    fn render_content(
+        &self,
+        memory: &Memory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let tags_display = if memory.tags.is_empty() {
+            "None".to_string()
+        } else {
+            memory.tags.join(", ")
+        };
+
+        let (type_row, tags_row) = match &self.editor {
+            Some(editor) => (
+                self.render_metadata_field(
+                    "Type",
+                    Select::new(&editor.type_select).w(px(200.)),
+                    cx,
+                )
+                .into_any_element(),
+                self.render_metadata_field("Tags", Input::new(&editor.tags_input).w_full(), cx)
+                    .into_any_element(),
+            ),
+            None => (
+                self.render_metadata_field(
+                    "Type",
+                    h_flex().child(type_badge(&memory.memory_type, cx)),
+                    cx,
+                )
+                .into_any_element(),
+                self.render_metadata_row("Tags", &tags_display, cx)
+                    .into_any_element(),
+            ),
+        };
+
         v_flex()
             .id("slideout-content")
             .w_full()
@@ -76,21 +659,29 @@ impl MemorySlideout {
             .p_4()
             .gap_4()
             .overflow_y_scroll()
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .w_full()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
             // Document section
             .child(
                 v_flex()
                     .w_full()
-                    .child(self.render_section("Document", cx))
                     .child(
-                        div()
+                        h_flex()
                             .w_full()
-                            .p_3()
-                            .rounded_md()
-                            .bg(cx.theme().sidebar)
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .child(memory.content.clone()),
-                    ),
+                            .justify_between()
+                            .child(self.render_section("Document", cx))
+                            .when(self.editor.is_none(), |this| {
+                                this.child(self.render_document_toolbar(memory, cx))
+                            }),
+                    )
+                    .child(self.render_document(memory, window, cx)),
             )
             // Metadata section
             .child(
@@ -109,28 +700,49 @@ impl MemorySlideout {
                         ),
                     )
                     .child(self.render_metadata_row("Author", &memory.created_by, cx))
-                    .child(self.render_metadata_row("Type", &memory.memory_type.to_string(), cx))
+                    .child(type_row)
+                    .child(tags_row)
                     .child(self.render_metadata_row(
-                        "Tags",
-                        &if memory.tags.is_empty() {
-                            "None".to_string()
-                        } else {
-                            memory.tags.join(", ")
-                        },
+                        "Visibility",
+                        &memory.visibility.to_string(),
                         cx,
-                    )),
+                    ))
+                    .when(memory.visibility == Visibility::Shared, |this| {
+                        this.child(self.render_metadata_row(
+                            "Shared With",
+                            &if memory.shared_with.is_empty() {
+                                "No one".to_string()
+                            } else {
+                                memory.shared_with.join(", ")
+                            },
+                            cx,
+                        ))
+                    }),
             )
-            // Related Memories placeholder
+            // Promote to knowledgebase
+            .when_some(self.promote_form.as_ref(), |this, form| {
+                this.child(
+                    v_flex()
+                        .w_full()
+                        .child(self.render_section("Promote to Knowledgebase", cx))
+                        .child(self.render_promote_form(form, cx)),
+                )
+            })
+            // Sharing editor
+            .when_some(self.sharing_editor.clone(), |this, sharing_editor| {
+                this.child(
+                    v_flex()
+                        .w_full()
+                        .child(self.render_section("Sharing", cx))
+                        .child(sharing_editor),
+                )
+            })
+            // Related memories
             .child(
                 v_flex()
                     .w_full()
                     .child(self.render_section("Related Memories", cx))
-                    .child(
-                        div()
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .child("Coming soon..."),
-                    ),
+                    .child(self.related.clone()),
             )
     }
 
@@ -139,6 +751,15 @@ impl MemorySlideout {
         label: &str,
         value: &str,
         cx: &Context<Self>,
+    ) -> impl IntoElement {
+        self.render_metadata_field(label, div().text_sm().child(value.to_string()), cx)
+    }
+
+    fn render_metadata_field(
+        &self,
+        label: &str,
+        value: impl IntoElement,
+        cx: &Context<Self>,
     ) -> impl IntoElement {
         h_flex()
             .w_full()
@@ -150,12 +771,12 @@ impl MemorySlideout {
                     .text_color(cx.theme().muted_foreground)
                     .child(label.to_string()),
             )
-            .child(div().flex_1().text_sm().child(value.to_string()))
+            .child(div().flex_1().child(value))
     }
 }
 
 impl Render for MemorySlideout {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let Some(memory) = &self.memory else {
             return div().into_any_element();
         };
@@ -167,7 +788,7 @@ impl Render for MemorySlideout {
             .border_color(cx.theme().border)
             .bg(cx.theme().background)
             .child(self.render_header(memory, cx))
-            .child(self.render_content(memory, cx))
+            .child(self.render_content(memory, window, cx))
             .into_any_element()
     }
 }
@@ -1,63 +1,71 @@
+use super::badge::type_badge;
+use super::columns::{
+    render_header, track_resize, Column, LayoutTable, SortDirection, TableLayout,
+};
+use crate::config::AppConfig;
 use crate::memory::Memory;
 use gpui::*;
-use gpui_component::{h_flex, list::ListItem, v_flex, ActiveTheme};
+use gpui_component::{
+    checkbox::Checkbox, h_flex, list::ListItem, v_flex, ActiveTheme, Disableable,
+};
+use std::collections::{HashMap, HashSet};
 
-struct MemoryTableHeader;
-
-impl MemoryTableHeader {
-    fn render(cx: &App) -> impl IntoElement {
-        h_flex()
-            .w_full()
-            .px_3()
-            .py_2()
-            .border_b_1()
-            .border_color(cx.theme().border)
-            .bg(cx.theme().muted)
-            .child(
-                div()
-                    .w(px(200.))
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("ID"),
-            )
-            .child(
-                div()
-                    .flex_1()
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("Document"),
-            )
-            .child(
-                div()
-                    .w(px(140.))
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("Created"),
-            )
-            .child(
-                div()
-                    .w(px(200.))
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("Author"),
-            )
-            .child(
-                div()
-                    .w(px(200.))
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("Tags"),
-            )
-            .child(
-                div()
-                    .w(px(80.))
-                    .text_sm()
-                    .font_weight(FontWeight::SEMIBOLD)
-                    .child("Type"),
-            )
-    }
+#[derive(Debug, Clone)]
+pub enum MemoryTableEvent {
+    SelectionChanged,
 }
 
+impl EventEmitter<MemoryTableEvent> for MemoryTable {}
+
+/// Width of the checkbox cell in front of each row
+const SELECT_CELL_WIDTH: f32 = 28.;
+
+/// Columns of the memory table, widths in pixels
+const COLUMNS: &[Column] = &[
+    Column {
+        id: "id",
+        label: "ID",
+        width: Some(200.),
+        sortable: true,
+    },
+    Column {
+        id: "document",
+        label: "Document",
+        width: None,
+        sortable: false,
+    },
+    Column {
+        id: "created",
+        label: "Created",
+        width: Some(140.),
+        sortable: true,
+    },
+    Column {
+        id: "author",
+        label: "Author",
+        width: Some(200.),
+        sortable: true,
+    },
+    Column {
+        id: "tags",
+        label: "Tags",
+        width: Some(200.),
+        sortable: true,
+    },
+    Column {
+        id: "type",
+        label: "Type",
+        width: Some(130.),
+        sortable: true,
+    },
+    Column {
+        id: "visibility",
+        label: "Visibility",
+        width: Some(100.),
+        sortable: false,
+    },
+];
+
 struct MemoryTableRow;
 
 impl MemoryTableRow {
@@ -65,11 +73,13 @@ impl MemoryTableRow {
         index: usize,
         memory: &Memory,
         is_selected: bool,
+        layout: &TableLayout,
         entity: &Entity<MemoryTable>,
         cx: &App,
     ) -> impl IntoElement {
         let memory_clone = memory.clone();
         let entity_clone = entity.clone();
+        let muted = cx.theme().muted_foreground;
 
         // Format date
         let created = memory.created_at.format("%Y-%m-%d %H:%M").to_string();
@@ -88,14 +98,41 @@ impl MemoryTableRow {
         // Truncate ID for display
         let id_display: String = memory.id.chars().take(8).collect();
 
+        let entity_for_checkbox = entity.clone();
+        let select_cell = div()
+            .w(px(SELECT_CELL_WIDTH))
+            .flex_shrink_0()
+            // Keep the row's click from replacing the selection
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .child(
+                Checkbox::new(("memory-select", index))
+                    .checked(is_selected)
+                    .on_click(move |_checked, _window, cx| {
+                        entity_for_checkbox.update(cx, |this, cx| {
+                            this.toggle_selected(index);
+                            cx.emit(MemoryTableEvent::SelectionChanged);
+                            cx.notify();
+                        });
+                    }),
+            );
+
         ListItem::new(("memory-row", index))
             .px_3()
             .py_1()
             .selected(is_selected)
-            .on_click(move |_, window, cx| {
+            .on_click(move |event, window, cx| {
+                let modifiers = event.modifiers();
                 entity_clone.update(cx, |this, cx| {
-                    this.selected_index = Some(index);
-                    (this.on_select)(index, &memory_clone, window, cx);
+                    if modifiers.shift {
+                        this.select_range(index);
+                    } else if modifiers.control || modifiers.platform {
+                        this.toggle_selected(index);
+                    } else {
+                        this.selected_index = Some(index);
+                        this.selected_ids = HashSet::from([memory_clone.id.clone()]);
+                        (this.on_select)(index, &memory_clone, window, cx);
+                    }
+                    cx.emit(MemoryTableEvent::SelectionChanged);
                     cx.notify();
                 });
             })
@@ -103,50 +140,27 @@ impl MemoryTableRow {
                 h_flex()
                     .w_full()
                     .gap_0()
+                    .child(select_cell)
+                    .child(layout.cell("id").text_color(muted).child(id_display))
+                    .child(layout.cell("document").child(content_preview))
+                    .child(layout.cell("created").text_color(muted).child(created))
                     .child(
-                        div()
-                            .w(px(200.))
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .overflow_hidden()
-                            .child(id_display),
-                    )
-                    .child(
-                        div()
-                            .flex_1()
-                            .text_sm()
-                            .overflow_hidden()
-                            .child(content_preview),
-                    )
-                    .child(
-                        div()
-                            .w(px(140.))
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .child(created),
-                    )
-                    .child(
-                        div()
-                            .w(px(200.))
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .overflow_hidden()
+                        layout
+                            .cell("author")
+                            .text_color(muted)
                             .child(memory.created_by.clone()),
                     )
+                    .child(layout.cell("tags").text_color(muted).child(tags_display))
                     .child(
-                        div()
-                            .w(px(200.))
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .overflow_hidden()
-                            .child(tags_display),
+                        layout
+                            .cell("type")
+                            .child(type_badge(&memory.memory_type, cx)),
                     )
                     .child(
-                        div()
-                            .w(px(80.))
-                            .text_sm()
-                            .text_color(cx.theme().muted_foreground)
-                            .child(memory.memory_type.to_string()),
+                        layout
+                            .cell("visibility")
+                            .text_color(muted)
+                            .child(memory.sharing_summary()),
                     ),
             )
     }
@@ -154,7 +168,14 @@ impl MemoryTableRow {
 
 pub struct MemoryTable {
     memories: Vec<Memory>,
+    /// Position of each memory in the order the server returned them, restored
+    /// when the sort is cleared
+    server_order: HashMap<String, usize>,
+    /// Row last clicked, used as the anchor for shift-click ranges
     selected_index: Option<usize>,
+    /// IDs of every selected row
+    selected_ids: HashSet<String>,
+    layout: TableLayout,
     on_select: Box<dyn Fn(usize, &Memory, &mut Window, &mut App) + 'static>,
 }
 
@@ -163,21 +184,141 @@ impl MemoryTable {
     where
         F: Fn(usize, &Memory, &mut Window, &mut App) + 'static,
     {
+        let server_order = server_order(&memories);
         Self {
             memories,
+            server_order,
             selected_index: None,
+            selected_ids: HashSet::new(),
+            layout: TableLayout::new(COLUMNS, &AppConfig::load().tables.memory),
             on_select: Box::new(on_select),
         }
     }
 
+    /// Replace the rows, keeping selected memories selected if they are still present
     pub fn set_memories(&mut self, memories: Vec<Memory>) {
+        let selected_id = self
+            .selected_index
+            .and_then(|index| self.memories.get(index))
+            .map(|memory| memory.id.clone());
+
+        self.server_order = server_order(&memories);
         self.memories = memories;
-        self.selected_index = None;
+        self.selected_index =
+            selected_id.and_then(|id| self.memories.iter().position(|m| m.id == id));
+        self.sort_memories();
+        self.selected_ids
+            .retain(|id| self.memories.iter().any(|m| &m.id == id));
+    }
+
+    /// Add another page of rows, keeping the current sort order
+    pub fn append_memories(&mut self, memories: Vec<Memory>) {
+        for memory in &memories {
+            let position = self.server_order.len();
+            self.server_order
+                .entry(memory.id.clone())
+                .or_insert(position);
+        }
+        self.memories.extend(memories);
+        self.sort_memories();
+    }
+
+    /// Sort the loaded rows by the selected column, in server order if there is none
+    fn sort_memories(&mut self) {
+        let selected_id = self
+            .selected_index
+            .and_then(|index| self.memories.get(index))
+            .map(|memory| memory.id.clone());
+
+        match self.layout.sort() {
+            Some((column, direction)) => self.memories.sort_by(|a, b| {
+                let ordering = match column {
+                    "id" => a.id.cmp(&b.id),
+                    "created" => a.created_at.cmp(&b.created_at),
+                    "author" => a.created_by.cmp(&b.created_by),
+                    "tags" => a.tags.len().cmp(&b.tags.len()),
+                    "type" => a.memory_type.to_string().cmp(&b.memory_type.to_string()),
+                    _ => std::cmp::Ordering::Equal,
+                };
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            }),
+            None => {
+                let server_order = &self.server_order;
+                self.memories
+                    .sort_by_key(|memory| server_order.get(&memory.id).copied());
+            }
+        }
+        self.selected_index =
+            selected_id.and_then(|id| self.memories.iter().position(|m| m.id == id));
     }
 
     pub fn set_selected(&mut self, index: Option<usize>) {
         self.selected_index = index;
+        self.selected_ids = index
+            .and_then(|index| self.memories.get(index))
+            .map(|memory| HashSet::from([memory.id.clone()]))
+            .unwrap_or_default();
+    }
+
+    /// Selected memories in table order
+    pub fn selected_memories(&self) -> Vec<Memory> {
+        self.memories
+            .iter()
+            .filter(|m| self.selected_ids.contains(&m.id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn selection_count(&self) -> usize {
+        self.selected_ids.len()
     }
+
+    pub fn select_all(&mut self) {
+        self.selected_ids = self.memories.iter().map(|m| m.id.clone()).collect();
+    }
+
+    fn all_selected(&self) -> bool {
+        !self.memories.is_empty() && self.selected_ids.len() == self.memories.len()
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_index = None;
+        self.selected_ids.clear();
+    }
+
+    fn toggle_selected(&mut self, index: usize) {
+        let Some(memory) = self.memories.get(index) else {
+            return;
+        };
+        if !self.selected_ids.remove(&memory.id) {
+            self.selected_ids.insert(memory.id.clone());
+        }
+        self.selected_index = Some(index);
+    }
+
+    /// Select every row between the last clicked row and this one
+    fn select_range(&mut self, index: usize) {
+        let anchor = self.selected_index.unwrap_or(index);
+        let (start, end) = (anchor.min(index), anchor.max(index));
+        self.selected_ids.extend(
+            self.memories
+                .iter()
+                .skip(start)
+                .take(end - start + 1)
+                .map(|m| m.id.clone()),
+        );
+    }
+}
+
+fn server_order(memories: &[Memory]) -> HashMap<String, usize> {
+    memories
+        .iter()
+        .enumerate()
+        .map(|(index, memory)| (memory.id.clone(), index))
+        .collect()
 }
 
 impl Render for MemoryTable {
@@ -189,17 +330,32 @@ impl Render for MemoryTable {
             .iter()
             .enumerate()
             .map(|(index, memory)| {
-                let is_selected = self.selected_index == Some(index);
-                MemoryTableRow::render(index, memory, is_selected, &entity, cx)
+                let is_selected = self.selected_ids.contains(&memory.id);
+                MemoryTableRow::render(index, memory, is_selected, &self.layout, &entity, cx)
             })
             .collect();
 
-        v_flex()
-            .id("memory-table")
+        let select_all = div().w(px(SELECT_CELL_WIDTH)).flex_shrink_0().child(
+            Checkbox::new("memory-select-all")
+                .checked(self.all_selected())
+                .disabled(self.memories.is_empty())
+                .on_click(cx.listener(|this, _checked, _window, cx| {
+                    if this.all_selected() {
+                        this.clear_selection();
+                    } else {
+                        this.select_all();
+                    }
+                    cx.emit(MemoryTableEvent::SelectionChanged);
+                    cx.notify();
+                })),
+        );
+        let header = render_header(&self.layout, Some(select_all.into_any_element()), cx);
+
+        track_resize(v_flex().id("memory-table"), cx)
             .w_full()
             .flex_1()
             .overflow_hidden()
-            .child(MemoryTableHeader::render(cx))
+            .child(header)
             .child(
                 v_flex()
                     .id("memory-table-rows")
@@ -210,3 +366,19 @@ impl Render for MemoryTable {
             )
     }
 }
+
+impl LayoutTable for MemoryTable {
+    fn layout(&mut self) -> &mut TableLayout {
+        &mut self.layout
+    }
+
+    fn layout_changed(&mut self, cx: &mut Context<Self>) {
+        self.sort_memories();
+
+        let layout = self.layout.to_config();
+        if let Err(e) = AppConfig::update(|app_config| app_config.tables.memory = layout) {
+            eprintln!("Failed to save config: {}", e);
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,306 @@
+use crate::memory::Memory;
+use chrono::{Datelike, Duration, NaiveDate};
+use gpui::*;
+use gpui_component::{h_flex, v_flex, ActiveTheme};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::badge::type_badge;
+use super::MemoryView;
+
+/// Width of each actor lane
+const LANE_WIDTH: f32 = 240.;
+
+/// Width of the column holding the day or week label
+const PERIOD_WIDTH: f32 = 140.;
+
+/// Period memories are grouped into on the timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimelineGrouping {
+    #[default]
+    Day,
+    Week,
+}
+
+impl TimelineGrouping {
+    pub fn all() -> &'static [TimelineGrouping] {
+        &[TimelineGrouping::Day, TimelineGrouping::Week]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimelineGrouping::Day => "Day",
+            TimelineGrouping::Week => "Week",
+        }
+    }
+
+    /// First day of the period containing a date, weeks starting on Monday
+    fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            TimelineGrouping::Day => date,
+            TimelineGrouping::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+        }
+    }
+
+    fn period_label(&self, start: NaiveDate) -> String {
+        match self {
+            TimelineGrouping::Day => start.format("%a %Y-%m-%d").to_string(),
+            TimelineGrouping::Week => format!("Week of {}", start.format("%Y-%m-%d")),
+        }
+    }
+}
+
+/// Memories recorded in one day or week, split into lanes by actor
+#[derive(Debug, Clone)]
+pub struct TimelinePeriod {
+    pub start: NaiveDate,
+    /// Memories per actor, oldest first
+    pub lanes: BTreeMap<String, Vec<Memory>>,
+}
+
+/// Memories grouped by period (newest first) with one lane per `created_by` actor
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    pub actors: Vec<String>,
+    pub periods: Vec<TimelinePeriod>,
+}
+
+impl Timeline {
+    pub fn build(memories: &[Memory], grouping: TimelineGrouping) -> Self {
+        let actors: BTreeSet<String> = memories.iter().map(|m| m.created_by.clone()).collect();
+
+        let mut periods: BTreeMap<NaiveDate, BTreeMap<String, Vec<Memory>>> = BTreeMap::new();
+        for memory in memories {
+            let start = grouping.period_start(memory.created_at.date_naive());
+            periods
+                .entry(start)
+                .or_default()
+                .entry(memory.created_by.clone())
+                .or_default()
+                .push(memory.clone());
+        }
+
+        let periods = periods
+            .into_iter()
+            .rev()
+            .map(|(start, mut lanes)| {
+                for memories in lanes.values_mut() {
+                    memories.sort_by_key(|m| m.created_at);
+                }
+                TimelinePeriod { start, lanes }
+            })
+            .collect();
+
+        Self {
+            actors: actors.into_iter().collect(),
+            periods,
+        }
+    }
+}
+
+/// Grid of periods (rows) by actor lanes (columns)
+pub fn render_timeline(
+    memories: &[Memory],
+    grouping: TimelineGrouping,
+    selected_id: Option<&str>,
+    cx: &mut Context<MemoryView>,
+) -> impl IntoElement {
+    let timeline = Timeline::build(memories, grouping);
+
+    let header = h_flex()
+        .px_3()
+        .py_2()
+        .border_b_1()
+        .border_color(cx.theme().border)
+        .bg(cx.theme().muted)
+        .text_sm()
+        .font_weight(FontWeight::SEMIBOLD)
+        .child(div().w(px(PERIOD_WIDTH)).flex_shrink_0().child("Period"))
+        .children(timeline.actors.iter().map(|actor| {
+            div()
+                .w(px(LANE_WIDTH))
+                .flex_shrink_0()
+                .px_2()
+                .truncate()
+                .child(actor.clone())
+        }));
+
+    let rows: Vec<_> = timeline
+        .periods
+        .iter()
+        .map(|period| {
+            let lanes: Vec<_> = timeline
+                .actors
+                .iter()
+                .map(|actor| {
+                    let cards: Vec<_> = period
+                        .lanes
+                        .get(actor)
+                        .map(|memories| {
+                            memories
+                                .iter()
+                                .map(|memory| {
+                                    let selected = selected_id == Some(memory.id.as_str());
+                                    render_card(memory, selected, cx).into_any_element()
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    v_flex()
+                        .w(px(LANE_WIDTH))
+                        .flex_shrink_0()
+                        .px_2()
+                        .gap_1()
+                        .children(cards)
+                })
+                .collect();
+
+            h_flex()
+                .items_start()
+                .px_3()
+                .py_2()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(
+                    div()
+                        .w(px(PERIOD_WIDTH))
+                        .flex_shrink_0()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(grouping.period_label(period.start)),
+                )
+                .children(lanes)
+        })
+        .collect();
+
+    div()
+        .id("memory-timeline")
+        .size_full()
+        .overflow_scroll()
+        .child(v_flex().min_w_full().child(header).children(rows))
+}
+
+fn render_card(memory: &Memory, selected: bool, cx: &mut Context<MemoryView>) -> impl IntoElement {
+    let preview = memory
+        .content
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let opened = memory.clone();
+
+    v_flex()
+        .id(SharedString::from(format!("timeline-{}", memory.id)))
+        .w_full()
+        .p_2()
+        .gap_1()
+        .rounded_md()
+        .border_1()
+        .border_color(if selected {
+            cx.theme().primary
+        } else {
+            cx.theme().border
+        })
+        .cursor_pointer()
+        .hover(|style| style.bg(cx.theme().muted))
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(type_badge(&memory.memory_type, cx))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(memory.created_at.format("%H:%M").to_string()),
+                ),
+        )
+        .child(div().w_full().text_sm().truncate().child(preview))
+        .on_click(cx.listener(move |this, _, _window, cx| {
+            this.open_memory(opened.clone(), cx);
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::mock::MemoryBuilder;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn february(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_week_starts_on_monday() {
+        // 2026-02-04 is a Wednesday
+        let date = NaiveDate::from_ymd_opt(2026, 2, 4).unwrap();
+        assert_eq!(
+            TimelineGrouping::Week.period_start(date),
+            NaiveDate::from_ymd_opt(2026, 2, 2).unwrap()
+        );
+        assert_eq!(TimelineGrouping::Day.period_start(date), date);
+    }
+
+    #[test]
+    fn test_build_groups_by_day_and_actor() {
+        let memories = vec![
+            MemoryBuilder::new("m1", "Memory")
+                .created_by("staff-mentor")
+                .created_at(february(2, 9))
+                .build(),
+            MemoryBuilder::new("m2", "Memory")
+                .created_by("personal-assistant")
+                .created_at(february(4, 15))
+                .build(),
+            MemoryBuilder::new("m3", "Memory")
+                .created_by("personal-assistant")
+                .created_at(february(4, 8))
+                .build(),
+            MemoryBuilder::new("m4", "Memory")
+                .created_by("staff-mentor")
+                .created_at(february(4, 11))
+                .build(),
+        ];
+
+        let timeline = Timeline::build(&memories, TimelineGrouping::Day);
+
+        assert_eq!(timeline.actors, vec!["personal-assistant", "staff-mentor"]);
+        assert_eq!(timeline.periods.len(), 2);
+        assert_eq!(
+            timeline.periods[0].start,
+            NaiveDate::from_ymd_opt(2026, 2, 4).unwrap()
+        );
+
+        let assistant: Vec<&str> = timeline.periods[0].lanes["personal-assistant"]
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(assistant, vec!["m3", "m2"]);
+        assert!(!timeline.periods[1].lanes.contains_key("personal-assistant"));
+    }
+
+    #[test]
+    fn test_build_groups_by_week() {
+        let memories = vec![
+            MemoryBuilder::new("m1", "Memory")
+                .created_by("staff-mentor")
+                .created_at(february(2, 9))
+                .build(),
+            MemoryBuilder::new("m2", "Memory")
+                .created_by("staff-mentor")
+                .created_at(february(6, 9))
+                .build(),
+            MemoryBuilder::new("m3", "Memory")
+                .created_by("staff-mentor")
+                .created_at(february(9, 9))
+                .build(),
+        ];
+
+        let timeline = Timeline::build(&memories, TimelineGrouping::Week);
+
+        assert_eq!(timeline.periods.len(), 2);
+        assert_eq!(timeline.periods[1].lanes["staff-mentor"].len(), 2);
+    }
+}
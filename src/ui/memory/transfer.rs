@@ -0,0 +1,427 @@
+use crate::memory::transfer::{
+    export_memories, exports_dir, fetch_all, import, parse_jsonl, plan_import, ExportFormat,
+    ImportPlan, ImportReport,
+};
+use crate::memory::{BerryClient, SearchRequest};
+use chrono::Utc;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::{
+    button::{Button, ButtonVariants},
+    form::{field, v_form},
+    h_flex,
+    input::{Input, InputState},
+    v_flex, ActiveTheme, Disableable, IconName, Selectable, Sizable,
+};
+use gpui_tokio_bridge::{JoinError, Tokio};
+use std::path::PathBuf;
+
+/// Number of duplicate records listed in the import preview
+const PREVIEW_LIMIT: usize = 10;
+
+#[derive(Debug, Clone)]
+pub enum MemoryTransferEvent {
+    Imported,
+    Close,
+}
+
+impl EventEmitter<MemoryTransferEvent> for MemoryTransferPanel {}
+
+/// Export memories to JSONL or Markdown and import JSONL exports
+pub struct MemoryTransferPanel {
+    client: BerryClient,
+    /// Search shown in the memory table when the panel was opened
+    search: SearchRequest,
+    export_all: bool,
+    import_path_input: Entity<InputState>,
+    plan: Option<ImportPlan>,
+    busy: bool,
+    message: Option<String>,
+    error: Option<String>,
+}
+
+impl MemoryTransferPanel {
+    pub fn new(client: BerryClient, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let import_path_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Path to a .jsonl export..."));
+
+        Self {
+            client,
+            search: SearchRequest::default(),
+            export_all: false,
+            import_path_input,
+            plan: None,
+            busy: false,
+            message: None,
+            error: None,
+        }
+    }
+
+    /// Export from and import into a different Berry server
+    pub fn set_client(&mut self, client: BerryClient) {
+        self.client = client;
+        self.plan = None;
+        self.message = None;
+        self.error = None;
+    }
+
+    /// Use the given search when exporting the current results
+    pub fn set_search(&mut self, search: SearchRequest) {
+        self.search = search;
+        self.message = None;
+        self.error = None;
+    }
+
+    fn export_request(&self) -> SearchRequest {
+        if self.export_all {
+            SearchRequest {
+                as_actor: self.search.as_actor.clone(),
+                ..Default::default()
+            }
+        } else {
+            self.search.clone()
+        }
+    }
+
+    fn start(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.busy {
+            return false;
+        }
+        self.busy = true;
+        self.message = None;
+        self.error = None;
+        cx.notify();
+        true
+    }
+
+    fn finish(&mut self, result: Result<String, String>) {
+        self.busy = false;
+        match result {
+            Ok(message) => self.message = Some(message),
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn export(&mut self, format: ExportFormat, cx: &mut Context<Self>) {
+        let Some(dir) = exports_dir() else {
+            self.error = Some("Could not determine the data directory".to_string());
+            cx.notify();
+            return;
+        };
+        if !self.start(cx) {
+            return;
+        }
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let request = self.export_request();
+
+        let task = Tokio::spawn(cx, async move {
+            let memories = fetch_all(&client, request)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let path = dir.join(format!(
+                "memories-{}.{}",
+                Utc::now().format("%Y%m%d-%H%M%S"),
+                format.extension()
+            ));
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            std::fs::write(&path, export_memories(&memories, format)).map_err(|e| e.to_string())?;
+
+            Ok::<_, String>((path, memories.len()))
+        });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<(PathBuf, usize), String>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    this.finish(match result {
+                        Ok(Ok((path, count))) => Ok(format!(
+                            "Exported {} memories as {} to {}",
+                            count,
+                            format,
+                            path.display()
+                        )),
+                        Ok(Err(e)) => Err(e),
+                        Err(e) => Err(e.to_string()),
+                    });
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    /// Dry run of an import, checking the file against memories already in Berry
+    fn preview(&mut self, cx: &mut Context<Self>) {
+        let path = self.import_path_input.read(cx).text().to_string();
+        if path.trim().is_empty() {
+            self.error = Some("Enter the path of a JSONL export".to_string());
+            cx.notify();
+            return;
+        }
+        if !self.start(cx) {
+            return;
+        }
+        self.plan = None;
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+        let existing_request = SearchRequest {
+            as_actor: self.search.as_actor.clone(),
+            ..Default::default()
+        };
+
+        let task = Tokio::spawn(cx, async move {
+            let input = std::fs::read_to_string(path.trim()).map_err(|e| e.to_string())?;
+            let records = parse_jsonl(&input).map_err(|e| e.to_string())?;
+            let existing = fetch_all(&client, existing_request)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok::<_, String>(plan_import(records, &existing))
+        });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<Result<ImportPlan, String>, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    let result = match result {
+                        Ok(Ok(plan)) => {
+                            let message = format!(
+                                "{} new, {} duplicate(s) will be skipped",
+                                plan.new.len(),
+                                plan.duplicates.len()
+                            );
+                            this.plan = Some(plan);
+                            Ok(message)
+                        }
+                        Ok(Err(e)) => Err(e),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    this.finish(result);
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn run_import(&mut self, cx: &mut Context<Self>) {
+        let Some(plan) = self.plan.take() else {
+            return;
+        };
+        if !self.start(cx) {
+            self.plan = Some(plan);
+            return;
+        }
+
+        let entity = cx.entity().clone();
+        let client = self.client.clone();
+
+        let task = Tokio::spawn(cx, async move { import(&client, plan.new).await });
+
+        cx.spawn(async move |_this, cx| {
+            let result: Result<ImportReport, JoinError> = task.await;
+            cx.update(|cx: &mut App| {
+                entity.update(cx, |this, cx| {
+                    let result = match result {
+                        Ok(report) if report.failed.is_empty() => {
+                            Ok(format!("Imported {} memories", report.imported))
+                        }
+                        Ok(report) => Err(format!(
+                            "Imported {} memories, {} failed: {}",
+                            report.imported,
+                            report.failed.len(),
+                            report.failed[0].1
+                        )),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    this.finish(result);
+                    cx.emit(MemoryTransferEvent::Imported);
+                    cx.notify();
+                });
+            })
+        })
+        .detach();
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_4()
+            .py_3()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .justify_between()
+            .child(
+                div()
+                    .text_base()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child("Export & Import"),
+            )
+            .child(
+                Button::new("close-transfer")
+                    .icon(IconName::Close)
+                    .ghost()
+                    .xsmall()
+                    .on_click(cx.listener(|_this, _, _window, cx| {
+                        cx.emit(MemoryTransferEvent::Close);
+                    })),
+            )
+    }
+
+    fn render_export(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let location = exports_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default();
+
+        v_form()
+            .child(
+                field().label("Export").child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Button::new("export-scope-search")
+                                .label("Current Search")
+                                .xsmall()
+                                .outline()
+                                .selected(!self.export_all)
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.export_all = false;
+                                    cx.notify();
+                                })),
+                        )
+                        .child(
+                            Button::new("export-scope-all")
+                                .label("All Memories")
+                                .xsmall()
+                                .outline()
+                                .selected(self.export_all)
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.export_all = true;
+                                    cx.notify();
+                                })),
+                        ),
+                ),
+            )
+            .child(
+                field()
+                    .description(format!("Files are written to {}", location))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new("export-jsonl")
+                                    .label("Export JSONL")
+                                    .small()
+                                    .disabled(self.busy)
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        this.export(ExportFormat::Jsonl, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("export-markdown")
+                                    .label("Export Markdown")
+                                    .small()
+                                    .disabled(self.busy)
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        this.export(ExportFormat::Markdown, cx);
+                                    })),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_import(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        v_form()
+            .child(
+                field()
+                    .label("Import")
+                    .description("JSONL exported from any Berry instance")
+                    .child(Input::new(&self.import_path_input).w_full()),
+            )
+            .child(
+                field().child(
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            Button::new("preview-import")
+                                .label("Preview")
+                                .small()
+                                .disabled(self.busy)
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.preview(cx);
+                                })),
+                        )
+                        .when_some(self.plan.as_ref(), |this, plan| {
+                            this.child(
+                                Button::new("run-import")
+                                    .label(format!("Import {} Memories", plan.new.len()))
+                                    .primary()
+                                    .small()
+                                    .disabled(self.busy || plan.new.is_empty())
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        this.run_import(cx);
+                                    })),
+                            )
+                        }),
+                ),
+            )
+    }
+
+    fn render_duplicates(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let plan = self.plan.as_ref()?;
+        if plan.duplicates.is_empty() {
+            return None;
+        }
+
+        let more = plan.duplicates.len().saturating_sub(PREVIEW_LIMIT);
+
+        Some(
+            v_flex()
+                .gap_1()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child("Already in Berry:")
+                .children(plan.duplicates.iter().take(PREVIEW_LIMIT).map(|memory| {
+                    div().truncate().child(format!(
+                        "{} - {}",
+                        memory.id,
+                        memory.content.lines().next().unwrap_or_default()
+                    ))
+                }))
+                .when(more > 0, |this| this.child(format!("and {} more", more))),
+        )
+    }
+}
+
+impl Render for MemoryTransferPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(px(600.))
+            .h_full()
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().background)
+            .child(self.render_header(cx))
+            .child(
+                v_flex()
+                    .id("transfer-content")
+                    .w_full()
+                    .flex_1()
+                    .p_4()
+                    .gap_6()
+                    .overflow_y_scroll()
+                    .child(self.render_export(cx))
+                    .child(self.render_import(cx))
+                    .when_some(self.message.clone(), |this, message| {
+                        this.child(div().text_sm().child(message))
+                    })
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                    })
+                    .children(self.render_duplicates(cx)),
+            )
+    }
+}
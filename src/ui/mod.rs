@@ -6,8 +6,8 @@ pub mod theme;
 pub mod window;
 mod workspace;
 
-pub use memory::MemoryView;
+pub use memory::{MemoryView, MemoryViewEvent};
 pub use navigation::NavigationBar;
 pub use persona::PersonaPanel;
-pub use settings::SettingsView;
+pub use settings::{SettingsEvent, SettingsView};
 pub use workspace::{FooterBar, HeaderBar};
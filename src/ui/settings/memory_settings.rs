@@ -1,15 +1,40 @@
-use crate::config::{AppConfig, BerryConfig};
+use crate::config::{AppConfig, BerryAuthScheme, BerryConfig, BerryProfile, Secrets};
+use crate::memory::MemoryCache;
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::form::{field, v_form};
 use gpui_component::input::{Input, InputState};
-use gpui_component::{h_flex, label::Label, v_flex, ActiveTheme};
+use gpui_component::select::{Select, SelectEvent, SelectState};
+use gpui_component::{
+    h_flex, label::Label, v_flex, ActiveTheme, Disableable, IconName, IndexPath, Sizable,
+};
 use std::path::PathBuf;
 
+#[derive(Debug, Clone)]
+pub enum MemorySettingsEvent {
+    /// Berry profiles or the active profile were saved
+    BerryChanged,
+}
+
+impl EventEmitter<MemorySettingsEvent> for MemorySettingsPanel {}
+
 pub struct MemorySettingsPanel {
     berry_config: BerryConfig,
+    /// Name of the profile shown in the form
+    editing: String,
+    /// Token for the edited profile from the secrets file
+    berry_token: String,
     knowledgebase_dir: PathBuf,
+    /// Why the last save was refused
+    error: Option<String>,
+    profile_select: Option<Entity<SelectState<Vec<String>>>>,
+    name_input: Option<Entity<InputState>>,
     berry_url_input: Option<Entity<InputState>>,
+    auth_select: Option<Entity<SelectState<Vec<String>>>>,
+    auth_header_input: Option<Entity<InputState>>,
+    token_input: Option<Entity<InputState>>,
+    default_actor_input: Option<Entity<InputState>>,
     kb_directory_input: Option<Entity<InputState>>,
 }
 
@@ -23,34 +48,188 @@ impl MemorySettingsPanel {
             .map(|p| p.join("knowledgebase"))
             .unwrap_or_else(|| PathBuf::from("knowledgebase"));
 
+        // Profiles as saved, without the environment overrides, since they are
+        // written back to the file
+        let berry_config = AppConfig::load_file().berry;
+
         Self {
-            berry_config: app_config.berry,
+            editing: berry_config.active().name.clone(),
+            berry_config,
+            berry_token: String::new(),
             knowledgebase_dir,
+            error: None,
+            profile_select: None,
+            name_input: None,
             berry_url_input: None,
+            auth_select: None,
+            auth_header_input: None,
+            token_input: None,
+            default_actor_input: None,
             kb_directory_input: None,
         }
     }
 
+    /// Track a profile made active elsewhere, such as the memory view's switcher
+    pub fn set_active_profile(&mut self, name: &str, cx: &mut Context<Self>) {
+        if self.berry_config.set_active(name) {
+            cx.notify();
+        }
+    }
+
     fn initialize(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let profile_select = cx.new(|cx| {
+            SelectState::new(
+                self.berry_config.profile_names(),
+                self.profile_index(),
+                window,
+                cx,
+            )
+        });
+        cx.subscribe_in(
+            &profile_select,
+            window,
+            |this, _select, event: &SelectEvent<Vec<String>>, window, cx| {
+                if let SelectEvent::Confirm(Some(name)) = event {
+                    this.edit_profile(name.clone(), window, cx);
+                }
+            },
+        )
+        .detach();
+        self.profile_select = Some(profile_select);
+
+        self.load_profile_form(window, cx);
+
+        self.kb_directory_input = Some(cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Knowledgebase directory...")
+                .default_value(self.knowledgebase_dir.to_string_lossy().to_string())
+        }));
+    }
+
+    fn profile_index(&self) -> Option<IndexPath> {
+        self.berry_config
+            .profiles
+            .iter()
+            .position(|p| p.name == self.editing)
+            .map(IndexPath::new)
+    }
+
+    fn editing_profile(&self) -> &BerryProfile {
+        self.berry_config
+            .profile(&self.editing)
+            .unwrap_or_else(|| self.berry_config.active())
+    }
+
+    /// Fill the form with the edited profile and its token
+    fn load_profile_form(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let profile = self.editing_profile().clone();
+        self.berry_token = Secrets::load().token_for(&profile.name).unwrap_or_default();
+
+        self.name_input = Some(cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Profile name...")
+                .default_value(&profile.name)
+        }));
+
         self.berry_url_input = Some(cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder("Berry server URL...")
-                .default_value(&self.berry_config.server_url)
+                .default_value(&profile.server_url)
         }));
 
-        self.kb_directory_input = Some(cx.new(|cx| {
+        let scheme_names: Vec<String> = BerryAuthScheme::all()
+            .iter()
+            .map(|scheme| scheme.to_string())
+            .collect();
+        let scheme_index = BerryAuthScheme::all()
+            .iter()
+            .position(|scheme| *scheme == profile.auth)
+            .map(IndexPath::new);
+        let auth_select = cx.new(|cx| SelectState::new(scheme_names, scheme_index, window, cx));
+        cx.observe(&auth_select, |_this, _select, cx| cx.notify())
+            .detach();
+        self.auth_select = Some(auth_select);
+
+        self.auth_header_input = Some(cx.new(|cx| {
             InputState::new(window, cx)
-                .placeholder("Knowledgebase directory...")
-                .default_value(self.knowledgebase_dir.to_string_lossy().to_string())
+                .placeholder("Header name...")
+                .default_value(&profile.auth_header)
+        }));
+
+        self.token_input = Some(cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Token...")
+                .masked(true)
+                .default_value(&self.berry_token)
+        }));
+
+        self.default_actor_input = Some(cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("persona-ui")
+                .default_value(profile.default_actor.clone().unwrap_or_default())
         }));
     }
 
+    /// Show another profile in the form, discarding unsaved edits
+    fn edit_profile(&mut self, name: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.editing = name;
+        self.error = None;
+        self.refresh_profile_select(window, cx);
+        self.load_profile_form(window, cx);
+        cx.notify();
+    }
+
+    fn refresh_profile_select(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let names = self.berry_config.profile_names();
+        let index = self.profile_index();
+        if let Some(ref select) = self.profile_select {
+            select.update(cx, |select, cx| {
+                select.set_items(names, window, cx);
+                select.set_selected_index(index, window, cx);
+            });
+        }
+    }
+
+    fn input_text(input: &Option<Entity<InputState>>, cx: &Context<Self>) -> String {
+        input
+            .as_ref()
+            .map(|input| input.read(cx).text().to_string())
+            .unwrap_or_default()
+    }
+
+    fn selected_scheme(&self, cx: &Context<Self>) -> BerryAuthScheme {
+        self.auth_select
+            .as_ref()
+            .and_then(|select| select.read(cx).selected_value().cloned())
+            .and_then(|name| {
+                BerryAuthScheme::all()
+                    .iter()
+                    .find(|scheme| scheme.to_string() == name)
+                    .copied()
+            })
+            .unwrap_or(self.editing_profile().auth)
+    }
+
+    /// The edited profile as currently entered in the form
+    fn form_profile(&self, cx: &Context<Self>) -> BerryProfile {
+        let default_actor = Self::input_text(&self.default_actor_input, cx);
+        BerryProfile {
+            name: Self::input_text(&self.name_input, cx).trim().to_string(),
+            server_url: Self::input_text(&self.berry_url_input, cx),
+            auth: self.selected_scheme(cx),
+            auth_header: Self::input_text(&self.auth_header_input, cx),
+            default_actor: Some(default_actor.trim().to_string()).filter(|a| !a.is_empty()),
+        }
+    }
+
     fn is_dirty(&self, cx: &Context<Self>) -> bool {
-        if let Some(ref input) = self.berry_url_input {
-            let current = input.read(cx).text().to_string();
-            if current != self.berry_config.server_url {
-                return true;
-            }
+        if self.name_input.is_some() && self.form_profile(cx) != *self.editing_profile() {
+            return true;
+        }
+
+        if self.token_input.is_some() && Self::input_text(&self.token_input, cx) != self.berry_token
+        {
+            return true;
         }
 
         if let Some(ref input) = self.kb_directory_input {
@@ -63,11 +242,64 @@ impl MemorySettingsPanel {
         false
     }
 
-    fn save_config(&mut self, cx: &mut Context<Self>) {
-        if let Some(ref input) = self.berry_url_input {
-            input.update(cx, |state, _cx| {
-                self.berry_config.server_url = state.text().to_string();
-            });
+    /// Write the profiles to `config.toml`, keeping the active profile already on disk
+    /// since it may have been switched from the memory view
+    fn persist_profiles(&mut self, renamed: Option<(&str, &str)>) {
+        let profiles = self.berry_config.profiles.clone();
+        let mut active = self.berry_config.active_profile.clone();
+        let result = AppConfig::update(|app_config| {
+            let berry = &mut app_config.berry;
+            let mut saved_active = berry.active_profile.clone();
+            if let Some((old, new)) = renamed {
+                if saved_active == old {
+                    saved_active = new.to_string();
+                }
+            }
+
+            berry.profiles = profiles;
+            if !berry.set_active(&saved_active) {
+                berry.active_profile = berry.profiles[0].name.clone();
+            }
+            active = berry.active_profile.clone();
+        });
+        self.berry_config.active_profile = active;
+
+        if let Err(e) = result {
+            eprintln!("Failed to save config: {}", e);
+        }
+    }
+
+    fn save_config(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let profile = self.form_profile(cx);
+        let old_name = self.editing.clone();
+        if profile.name.is_empty() {
+            self.error = Some("Profile name can't be empty".to_string());
+            cx.notify();
+            return;
+        }
+        if profile.name != old_name && self.berry_config.profile(&profile.name).is_some() {
+            self.error = Some(format!("A profile named {} already exists", profile.name));
+            cx.notify();
+            return;
+        }
+        // Profiles whose names differ only in punctuation would share a cache file
+        if let Some(other) =
+            self.berry_config.profiles.iter().find(|p| {
+                p.name != old_name && MemoryCache::profiles_collide(&p.name, &profile.name)
+            })
+        {
+            self.error = Some(format!(
+                "Profile {} already uses the same cache as {}",
+                other.name, profile.name
+            ));
+            cx.notify();
+            return;
+        }
+        let token = Self::input_text(&self.token_input, cx);
+        if let Err(e) = profile.credentials(Some(token.clone())).validate() {
+            self.error = Some(e.to_string());
+            cx.notify();
+            return;
         }
 
         if let Some(ref input) = self.kb_directory_input {
@@ -76,19 +308,154 @@ impl MemorySettingsPanel {
             });
         }
 
-        let mut app_config = AppConfig::load();
-        app_config.berry = self.berry_config.clone();
-        if let Err(e) = app_config.save() {
-            eprintln!("Failed to save config: {}", e);
+        let name = profile.name.clone();
+        if name != old_name {
+            // Queued offline changes belong to the profile, so its cache moves with it
+            if let Err(e) = MemoryCache::rename_profile(&old_name, &name) {
+                self.error = Some(format!("Failed to move the profile's cache: {}", e));
+                cx.notify();
+                return;
+            }
+        }
+        if let Some(existing) = self
+            .berry_config
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == old_name)
+        {
+            *existing = profile;
+        }
+        self.persist_profiles(Some((&old_name, &name)));
+
+        // The token goes to the secrets file, never config.toml
+        if token != self.berry_token || name != old_name {
+            let mut secrets = Secrets::load();
+            if name != old_name {
+                secrets.set_token(&old_name, None);
+            }
+            secrets.set_token(&name, Some(token.clone()));
+            match secrets.save() {
+                Ok(()) => self.berry_token = token,
+                Err(e) => eprintln!("Failed to save secrets: {}", e),
+            }
         }
 
+        self.editing = name;
+        self.error = None;
+        self.refresh_profile_select(window, cx);
+        cx.emit(MemorySettingsEvent::BerryChanged);
+        cx.notify();
+    }
+
+    fn add_profile(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = (self.berry_config.profiles.len() + 1..)
+            .map(|n| format!("profile-{}", n))
+            .find(|name| self.berry_config.profile(name).is_none())
+            .unwrap_or_default();
+
+        self.berry_config.profiles.push(BerryProfile {
+            name: name.clone(),
+            ..Default::default()
+        });
+        self.persist_profiles(None);
+        self.edit_profile(name, window, cx);
+        cx.emit(MemorySettingsEvent::BerryChanged);
+    }
+
+    fn delete_profile(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.berry_config.profiles.len() < 2 {
+            return;
+        }
+
+        let name = self.editing.clone();
+        self.berry_config.profiles.retain(|p| p.name != name);
+        self.persist_profiles(None);
+
+        let mut secrets = Secrets::load();
+        secrets.set_token(&name, None);
+        if let Err(e) = secrets.save() {
+            eprintln!("Failed to save secrets: {}", e);
+        }
+        if let Err(e) = MemoryCache::delete_profile(&name) {
+            eprintln!("Failed to remove the profile's memory cache: {}", e);
+        }
+
+        let active = self.berry_config.active_profile.clone();
+        self.edit_profile(active, window, cx);
+        cx.emit(MemorySettingsEvent::BerryChanged);
+    }
+
+    /// Make the edited profile the one the memory view connects to
+    fn activate_profile(&mut self, cx: &mut Context<Self>) {
+        if self.berry_config.profile(&self.editing).is_none() {
+            return;
+        }
+        let editing = self.editing.clone();
+        if let Err(e) = AppConfig::update(|app_config| {
+            app_config.berry.set_active(&editing);
+        }) {
+            eprintln!("Failed to save config: {}", e);
+            return;
+        }
+        self.berry_config.set_active(&self.editing);
+        cx.emit(MemorySettingsEvent::BerryChanged);
         cx.notify();
     }
+
+    fn render_profile_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_active = self.editing == self.berry_config.active_profile;
+        let can_delete = self.berry_config.profiles.len() > 1;
+
+        h_flex()
+            .gap_2()
+            .items_center()
+            .when_some(self.profile_select.clone(), |this, select| {
+                this.child(Select::new(&select).small().w(px(240.)))
+            })
+            .child(if is_active {
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("Active")
+                    .into_any_element()
+            } else {
+                Button::new("activate-profile")
+                    .label("Use This Profile")
+                    .outline()
+                    .small()
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.activate_profile(cx);
+                    }))
+                    .into_any_element()
+            })
+            .child(div().flex_1())
+            .child(
+                Button::new("add-profile")
+                    .icon(IconName::Plus)
+                    .label("Add Profile")
+                    .ghost()
+                    .small()
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.add_profile(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("delete-profile")
+                    .icon(IconName::Delete)
+                    .label("Delete Profile")
+                    .ghost()
+                    .small()
+                    .disabled(!can_delete)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.delete_profile(window, cx);
+                    })),
+            )
+    }
 }
 
 impl Render for MemorySettingsPanel {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        if self.berry_url_input.is_none() {
+        if self.profile_select.is_none() {
             self.initialize(window, cx);
         }
 
@@ -105,9 +472,9 @@ impl Render for MemorySettingsPanel {
                 Button::new("save")
                     .primary()
                     .label("Save Changes")
-                    .on_click(move |_, _window, cx| {
+                    .on_click(move |_, window, cx| {
                         entity.update(cx, |this, cx| {
-                            this.save_config(cx);
+                            this.save_config(window, cx);
                         });
                     }),
             );
@@ -115,6 +482,14 @@ impl Render for MemorySettingsPanel {
 
         // Berry panel
         let mut berry_form = v_form();
+        if let Some(ref input) = self.name_input {
+            berry_form = berry_form.child(
+                field()
+                    .label("Name")
+                    .description("Shown in the profile switcher")
+                    .child(Input::new(input).w_full()),
+            );
+        }
         if let Some(ref input) = self.berry_url_input {
             berry_form = berry_form.child(
                 field()
@@ -123,6 +498,52 @@ impl Render for MemorySettingsPanel {
                     .child(Input::new(input).w_full()),
             );
         }
+        if let Some(ref input) = self.default_actor_input {
+            berry_form = berry_form.child(
+                field()
+                    .label("Default Actor")
+                    .description("Actor the memory view browses as when this profile is active")
+                    .child(Input::new(input).w_full()),
+            );
+        }
+        if let Some(ref select) = self.auth_select {
+            berry_form = berry_form.child(
+                field()
+                    .label("Authentication")
+                    .description("How the token is sent to Berry")
+                    .child(Select::new(select).w_full()),
+            );
+        }
+        let scheme = self.selected_scheme(cx);
+        if scheme == BerryAuthScheme::Header {
+            if let Some(ref input) = self.auth_header_input {
+                berry_form = berry_form.child(
+                    field()
+                        .label("Header")
+                        .description("Name of the header carrying the token")
+                        .child(Input::new(input).w_full()),
+                );
+            }
+        }
+        if scheme != BerryAuthScheme::None {
+            if let Some(ref input) = self.token_input {
+                let description =
+                    if AppConfig::env_token(&self.berry_config, &self.editing).is_some() {
+                        "A token set in the environment is used instead of this token".to_string()
+                    } else {
+                        match Secrets::path() {
+                            Some(path) => format!("Stored in {}", path.display()),
+                            None => "Stored in the secrets file".to_string(),
+                        }
+                    };
+                berry_form = berry_form.child(
+                    field()
+                        .label("Token")
+                        .description(description)
+                        .child(Input::new(input).w_full()),
+                );
+            }
+        }
 
         let berry_panel = v_flex()
             .gap_3()
@@ -131,6 +552,10 @@ impl Render for MemorySettingsPanel {
             .border_1()
             .border_color(cx.theme().border)
             .child(Label::new("Berry").text_lg())
+            .child(self.render_profile_bar(cx))
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+            })
             .child(berry_form);
 
         // Knowledgebase panel
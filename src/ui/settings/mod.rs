@@ -4,7 +4,7 @@ mod personas_settings;
 mod terminal_settings;
 
 pub use general_settings::GeneralSettingsPanel;
-pub use memory_settings::MemorySettingsPanel;
+pub use memory_settings::{MemorySettingsEvent, MemorySettingsPanel};
 pub use personas_settings::PersonasSettingsPanel;
 pub use terminal_settings::TerminalSettingsPanel;
 
@@ -49,6 +49,14 @@ impl SettingsCategory {
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum SettingsEvent {
+    /// Berry profiles or the active profile were saved
+    BerryChanged,
+}
+
+impl EventEmitter<SettingsEvent> for SettingsView {}
+
 pub struct SettingsView {
     selected_category: SettingsCategory,
     general_panel: Entity<GeneralSettingsPanel>,
@@ -62,6 +70,13 @@ impl SettingsView {
         let general_panel = cx.new(|_cx| GeneralSettingsPanel::new());
         let personas_panel = cx.new(|_cx| PersonasSettingsPanel::new());
         let memory_panel = cx.new(|_cx| MemorySettingsPanel::new());
+        cx.subscribe(
+            &memory_panel,
+            |_this, _panel, event: &MemorySettingsEvent, cx| match event {
+                MemorySettingsEvent::BerryChanged => cx.emit(SettingsEvent::BerryChanged),
+            },
+        )
+        .detach();
         let terminal_panel = cx.new(|_cx| TerminalSettingsPanel::new());
 
         Self {
@@ -73,6 +88,13 @@ impl SettingsView {
         }
     }
 
+    /// Show a profile made active from the memory view
+    pub fn set_active_berry_profile(&mut self, name: &str, cx: &mut Context<Self>) {
+        self.memory_panel.update(cx, |panel, cx| {
+            panel.set_active_profile(name, cx);
+        });
+    }
+
     fn render_category_list(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
         let categories = SettingsCategory::all();
 
@@ -1,15 +1,60 @@
+use crate::memory::BerryStatus;
+use crate::ui::memory::BerryHealthMonitor;
 use gpui::*;
-use gpui_component::ActiveTheme;
+use gpui_component::{h_flex, ActiveTheme};
 
-pub struct FooterBar;
+pub struct FooterBar {
+    health: Entity<BerryHealthMonitor>,
+}
 
 impl FooterBar {
-    pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
-        Self
+    pub fn new(
+        health: Entity<BerryHealthMonitor>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.observe(&health, |_this, _health, cx| cx.notify())
+            .detach();
+
+        Self { health }
+    }
+
+    pub fn view(
+        health: Entity<BerryHealthMonitor>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        cx.new(|cx| Self::new(health, window, cx))
     }
 
-    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
-        cx.new(|cx| Self::new(window, cx))
+    fn render_berry_status(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let health = self.health.read(cx);
+        let status = health.status();
+
+        let color = match status {
+            BerryStatus::Unknown => cx.theme().muted_foreground,
+            BerryStatus::Online { .. } => cx.theme().success,
+            BerryStatus::Offline(_) => cx.theme().danger,
+            BerryStatus::Unauthorized(_) => cx.theme().warning,
+        };
+
+        let text = match status {
+            BerryStatus::Offline(_) => format!("Berry: {} ({})", status, health.server_url()),
+            BerryStatus::Unauthorized(_) => format!(
+                "Berry: {} ({}), check the token in Memory settings",
+                status,
+                health.server_url()
+            ),
+            _ => format!("Berry: {}", status),
+        };
+
+        h_flex()
+            .gap_2()
+            .items_center()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(div().size(px(8.)).rounded_full().bg(color))
+            .child(text)
     }
 }
 
@@ -20,8 +65,12 @@ impl Render for FooterBar {
             .w_full()
             .h(px(32.))
             .flex_shrink_0()
+            .flex()
+            .items_center()
+            .px_3()
             .bg(cx.theme().sidebar)
             .border_t_1()
             .border_color(cx.theme().border)
+            .child(self.render_berry_status(cx))
     }
 }